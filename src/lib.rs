@@ -52,6 +52,116 @@ use core::ops::FnOnce;
 /// // Foo::builder().x(1).y(2).y(3);
 /// ```
 ///
+/// The builder can also be reached through a type alias, which fixes the generic parameters of the
+/// builder the same way it fixes them for the aliased type:
+///
+/// ```
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo<T> {
+///     x: T,
+/// }
+///
+/// type Alias = Foo<u32>;
+///
+/// let foo: Alias = Alias::builder().x(Default::default()).build();
+/// assert_eq!(foo.x, 0);
+/// ```
+///
+/// Defaults of generic parameters carry over to the builder type, so `FooBuilder` names the
+/// builder of `Foo` with its defaulted parameters. Like with any generic type, the defaults are
+/// only used where the type is named, not for inference:
+///
+/// ```
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo<T = String> {
+///     x: T,
+/// }
+///
+/// let builder: FooBuilder = <Foo>::builder();
+/// assert_eq!(builder.x("bar".to_owned()).build().x, "bar");
+/// ```
+///
+/// # Unions
+///
+/// `TypedBuilder` can also be derived on unions. The builder has a setter for each member, and
+/// exactly one of them must be called before `.build()`:
+///
+/// ```
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(Clone, Copy, TypedBuilder)]
+/// union Number {
+///     int: u32,
+///     #[builder(setter(into))]
+///     float: f64,
+/// }
+///
+/// let number = Number::builder().float(1.5f32).build();
+/// assert_eq!(unsafe { number.float }, 1.5);
+///
+/// // This will not compile - because we set two members:
+/// // Number::builder().int(1).float(1.5).build();
+/// ```
+///
+/// Union members support most of the `setter(...)` settings. A member is set exactly once and
+/// never has a default, so the settings about defaults, mutators and repeated calls are rejected:
+/// `default`, `default_once`, `default_try`, `computed`, `via_mutators`, `mutators`,
+/// `mutable_during_default_resolution`, `missing_message`, and the `skip`, `lazy`, `each`,
+/// `entry`, `indexed`, `unit`, `setter_fn`, `field_mut`, `maybe_into`, `merge` and
+/// `try_into(deferred)` setter settings. Union builders cannot derive `Debug`, so `secret` is
+/// rejected as well.
+///
+/// # Tuple structs
+///
+/// For tuple structs, the builder refers to the fields as `_0`, `_1`, ... - these are the names of
+/// the setters unless they are given a `setter(name = ...)`, and the names the `default` expressions
+/// and the mutators use for the other fields:
+///
+/// ```
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(Debug, PartialEq, TypedBuilder)]
+/// struct Size(#[builder(setter(name = width))] u32, #[builder(default = _0)] u32);
+///
+/// assert_eq!(Size::builder().width(3).build(), Size(3, 3));
+/// assert_eq!(Size::builder().width(3)._1(4).build(), Size(3, 4));
+/// ```
+///
+/// # Enums
+///
+/// On enums, each struct-like variant gets its own builder, created with
+/// `<variant in snake_case>_builder()` and named `<Enum><Variant>Builder`. Tuple and unit variants
+/// get no builder, and an enum without struct-like variants is an error. The `#[builder(...)]`
+/// attributes of the enum apply to all the variants, and can be extended or overridden with
+/// `#[builder(...)]` attributes on each variant:
+///
+/// ```
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(Debug, PartialEq, TypedBuilder)]
+/// #[builder(field_defaults(setter(into)))]
+/// enum Command {
+///     Move {
+///         x: i32,
+///         #[builder(default)]
+///         y: i32,
+///     },
+///     #[builder(builder_method(name = say))]
+///     Say { text: String },
+///     Quit,
+/// }
+///
+/// assert_eq!(Command::move_builder().x(3).build(), Command::Move { x: 3, y: 0 });
+/// assert_eq!(Command::say().text("hello").build(), Command::Say { text: "hello".to_owned() });
+/// ```
+///
+/// `apply_method`, `merge_from_method`, `partial_type`, `constructor`, `invariant` and `reset` are
+/// not supported for enums.
+///
 /// # Customization with attributes
 ///
 /// In addition to putting `#[derive(TypedBuilder)]` on a type, you can specify a `#[builder(...)]`
@@ -84,12 +194,266 @@ use core::ops::FnOnce;
 ///     `build()` method of the builder type. Setting this implies `doc`.
 ///
 ///
+/// - The `builder_method(...)` subsection also has:
+///   - `params(name: Type, ...)`: make the builder method take arguments, each setting the field
+///     with the same name - e.g. `builder_method(name = with, params(id: u32))` generates
+///     `Foo::with(id)`. The parameters must have the types of their fields, and like other set
+///     fields, they are available to the defaults of the fields that come after them. Parameters
+///     that don't name a field with a setter are an error.
+///
 /// - The `build_method(...)` subsection also has:
 ///   - `into` or `into = ...`: change the output type of the builder. When a specific value/type
 ///     is set via the assignment, this will be the output type of the builder. If no specific
 ///     type is set, but `into` is specified, the return type will be generic and the user can
 ///     decide which type shall be constructed. In both cases an [`Into`] conversion is required to
 ///     be defined from the original type to the target type.
+///   - `returns_impl = ...`: make the build method return `impl ...` instead of the concrete type,
+///     e.g. `returns_impl = Shape + Send`. This hides the built type from the caller, who can only
+///     use it through the specified traits. Like any `impl Trait` return type, auto traits such
+///     as `Send` and `Sync` of the concrete type still leak through - list them explicitly if the
+///     caller should be able to rely on them. Cannot be combined with `into`.
+///   - `or_default`: also generate a `build_or_default()` method (named after the build method)
+///     that can be called in any state of the builder. Fields that were not set get their
+///     `default` if they have one, and `Default::default()` otherwise - which requires the types
+///     of all the required fields to implement [`Default`].
+///   - `pinned`: make the build method return the built value pinned in a box - a
+///     `Pin<Box<...>>` created with [`Box::pin`] - for types that must not move once they are
+///     built. Can be combined with `into` and `returns_impl`, in which case the converted value is
+///     pinned.
+///   - `post = |value: Foo| -> Result<Foo, E> { ... }`: pass the built value through a fallible
+///     closure that can normalize and validate it, making the build method return
+///     `Result<Foo, E>` - the error type is taken from the closure's return type, which must be
+///     written out. The closure runs last, after the automatic mutators and the `invariant`
+///     checks, and before the `into` conversion.
+///   - `error = ...`: the error type of the build method, for builders with `try_into(deferred)`
+///     setters or `default_try` defaults, making the build method return `Result<Foo, E>`. Not
+///     needed when `post` is set, whose error type is used instead.
+///
+/// - The `builder_type(...)` subsection also has:
+///   - `impl_trait = ...`: make the builder type implement the given trait, in all its states,
+///     with an empty `impl` block. This is meant for marker traits - e.g. the supertrait of a
+///     sealed trait - so that the builder can be used in generic bounds without exposing its
+///     internals. Can be specified multiple times. Traits with required items cannot be
+///     implemented this way, and the compiler will report the missing items.
+///   - `derive(...)`: implement standard traits for the builder type. The supported traits are
+///     `Clone` (which the builder type always implements anyway), `Debug` - which shows the fields
+///     that were already set - `PartialEq`, `Eq` and `Hash`. The builder implements them only when
+///     the types of its set fields do. `PartialEq`, `Eq` and `Hash` only look at the fields - a
+///     builder's context is ignored - so e.g. builders can be used as keys of a `HashMap`.
+///   - `cfg_derive(predicate, Trait, ...)`: like `derive(...)`, but the impls are only generated
+///     when the `cfg` predicate holds - e.g. `cfg_derive(feature = "debug", Debug)`. Can be
+///     specified multiple times with different predicates.
+///   - `debug(skip = [field1, field2, ...])`: implement `Debug` for the builder type like
+///     `derive(Debug)`, but never show the listed fields - e.g. passwords and tokens. The listed
+///     fields don't need to implement `Debug`, and listing a field that doesn't exist is an error.
+///
+/// - `apply_method` or `apply_method(...)`: generate a method on the builder that assigns the fields
+///   that were set on the builder to an existing instance, leaving all other fields - including the
+///   ones that have a default - untouched. The subsection accepts the same `vis`, `name` and `doc`
+///   fields as `build_method(...)`, and the name defaults to `apply_to`. With `defaults`, the method
+///   also assigns the defaults of the unset fields that have one - including the skipped fields -
+///   in which case the defaults cannot refer to fields without a default.
+///
+///   ```
+///   use typed_builder::TypedBuilder;
+///
+///   #[derive(PartialEq, Debug, TypedBuilder)]
+///   #[builder(apply_method)]
+///   struct Foo {
+///       x: i32,
+///       #[builder(default = 2)]
+///       y: i32,
+///   }
+///
+///   let mut foo = Foo { x: 10, y: 20 };
+///   Foo::builder().x(1).apply_to(&mut foo);
+///   assert_eq!(foo, Foo { x: 1, y: 20 });
+///   ```
+///
+/// - `merge_from_method` or `merge_from_method(...)`: generate a `merge_from(&mut self, other: &Self)`
+///   method on the type itself (not the builder), with protobuf merge semantics: every field of
+///   `other` that is not equal to its [`Default`] value overwrites the same field of `self`, and
+///   collection fields are extended with the items of `other` instead - `Vec`s and the other
+///   sequences are concatenated, sets are united and the entries of maps overwrite the existing
+///   entries with the same keys. Collections are recognized by name: `Vec`, `VecDeque`,
+///   `LinkedList`, `HashMap`, `BTreeMap`, `HashSet`, `BTreeSet` and `BinaryHeap`. Any other type,
+///   including other collections and aliases of these ones, is overwritten like a scalar. This
+///   requires the types of the fields to implement `PartialEq`, `Default` and `Clone` (or, for
+///   collection fields, `Clone` and [`Extend`] with their own items).
+///   The subsection accepts the same `vis`, `name` and `doc` fields as `build_method(...)`.
+///
+/// - `recipe` or `recipe(...)`: generate an `into_recipe()` method on builders that can be built,
+///   which turns the builder into a `FooRecipe` (the name can be changed with `name = ...`). The
+///   recipe keeps the set values and can build any number of instances with its `build(&self)`
+///   method, by cloning them - so the set values must be `Clone`. The defaults of the unset fields
+///   are evaluated again for every build, unless the subsection has `capture_defaults` - which
+///   makes `into_recipe()` evaluate them once and keep the values like the set ones, so their types
+///   must be `Clone` too. The defaults of skipped fields are still evaluated for every build.
+///   `capture_defaults` conflicts with `try_into(deferred)`. The recipe's `build_iter(self, count)`
+///   method returns an iterator that lazily builds `count` instances the same way. The subsection
+///   also accepts the same `vis`, `name` and `doc` fields as `build_method(...)`.
+///
+/// - `partial_type` or `partial_type(...)`: generate a plain struct - `FooPartial` by default - with
+///   an `Option` of every field, and a `with_overrides` method on the type that replaces the fields
+///   that are `Some` in the partial type. This is useful for layered configuration, since unlike the
+///   builder, the partial type can derive traits like `Default` or `serde::Deserialize` - pass them
+///   with `derive(...)`. Fields whose types have a partial type of their own are still replaced as
+///   a whole. The subsection also accepts the same `vis`, `name` and `doc` fields as
+///   `build_method(...)`.
+///
+/// - `context = Type`: make the builder method take a context object of that type, which the
+///   builder carries until it is built. Default expressions can refer to it as `context` (a
+///   reference), and mutators can access it with `self.context()`. The context type must be
+///   `Clone`, since the builder is, and no field can be named `context`.
+///
+/// - `max_fields = N`: fail the derive if the struct has more than `N` fields. The builder tracks
+///   every field in its type, so huge structs make for huge types and slow compilation - this turns
+///   an accidental growth past a chosen size into an error. Fields with `skip` and `computed` count
+///   too. There is no fallback to a builder without a type-state for large structs - every builder
+///   is a type-state builder, so going past the limit is always an error.
+///
+/// - `drop_order = [field1, field2, ...]`: verify that the listed fields are declared - and
+///   therefore dropped - in the listed order, e.g. a guard before the resource it guards. Rust always
+///   drops fields in declaration order, so this does not change the order, but it turns an
+///   accidental reordering of the fields into a compile error.
+///
+/// - `constructor(name(field1, field2, ...), ...)`: generate a constructor function with the given
+///   name on the type itself, that takes the listed fields - in the listed order, with their exact
+///   types - and builds the instance using the defaults for all the other fields. The listed fields
+///   must include every field without a default. Several constructors can be declared. They have
+///   the visibility of the builder method, return what the build method returns, and take the
+///   `context` first if there is one.
+///
+///   ```
+///   use typed_builder::TypedBuilder;
+///
+///   #[derive(PartialEq, Debug, TypedBuilder)]
+///   #[builder(constructor(new(name, age), with_email(name, age, email)))]
+///   struct Person {
+///       name: String,
+///       age: u32,
+///       #[builder(default)]
+///       email: Option<String>,
+///   }
+///
+///   assert_eq!(
+///       Person::new("Jane".to_owned(), 30),
+///       Person::builder().name("Jane".to_owned()).age(30).build());
+///   ```
+///
+/// - `invariant = expr`: check the condition `expr` with [`debug_assert!`] on the built value,
+///   which `expr` can refer to as `self`, in the build method. Like any `debug_assert!`, the check
+///   is compiled out in release builds. Can be specified multiple times, and the invariants are
+///   checked in the order they are specified.
+///
+///   ```should_panic
+///   use typed_builder::TypedBuilder;
+///
+///   #[derive(TypedBuilder)]
+///   #[builder(invariant = self.min <= self.max)]
+///   struct Range {
+///       min: i32,
+///       max: i32,
+///   }
+///
+///   # if !cfg!(debug_assertions) { panic!() }
+///   Range::builder().min(2).max(1).build(); // panics in debug builds
+///   ```
+///
+/// - `build_via = path::to::function`: make the build method create the instance by calling the
+///   given function with all the fields - including the skipped ones - in declaration order,
+///   instead of with a struct literal. This allows building types whose constructor enforces their
+///   invariants. A fallible constructor can be used together with `build_method(into = ...)` to make
+///   the build method return its `Result`. Cannot be combined with `invariant`.
+///
+///   ```
+///   use typed_builder::TypedBuilder;
+///
+///   #[derive(Debug, TypedBuilder)]
+///   #[builder(build_via = Even::new, build_method(into = Result<Even, String>))]
+///   struct Even {
+///       value: u32,
+///   }
+///
+///   impl Even {
+///       fn new(value: u32) -> Result<Self, String> {
+///           if value % 2 == 0 {
+///               Ok(Self { value })
+///           } else {
+///               Err(format!("{} is odd", value))
+///           }
+///       }
+///   }
+///
+///   assert_eq!(Even::builder().value(4).build().unwrap().value, 4);
+///   assert!(Even::builder().value(3).build().is_err());
+///   ```
+///
+/// - `short_circuit(field = flag, returns = expr)`: when the `bool` field `flag` is `true`, make
+///   the build method return `expr` - e.g. a clone of a cached value - instead of constructing the
+///   instance. Like default expressions, `expr` can refer to the values of all the fields. The
+///   type-state does not depend on the flag's value, so fields without a default must be set even
+///   when they are unnecessary in the short-circuit path.
+///
+/// - `reset`: generate an associated `into_fresh_builder()` function, that creates a builder with
+///   none of the fields set - like the builder method - and a `reset(self)` method, that discards
+///   the instance and creates such a builder, e.g. for object pools. Fields with `skip` and
+///   `via_mutators` are initialized as usual. Cannot be combined with `context` or
+///   `builder_method(params(...))`.
+///
+/// - `emit_schema`: generate a `SCHEMA` constant on the builder type - a `&str` describing each
+///   setter on its own line, with its parameters as they are after `into`, `strip_option` and the
+///   other setter settings, e.g. `name(name: impl Into<String>)`. Setters of fields with a default
+///   are marked `(optional)`. Meant for documentation generators and for debugging the setters the
+///   macro generates.
+///
+///   ```
+///   use typed_builder::TypedBuilder;
+///
+///   #[derive(TypedBuilder)]
+///   #[builder(emit_schema)]
+///   struct Foo {
+///       #[builder(setter(into))]
+///       name: String,
+///       #[builder(default, setter(strip_option))]
+///       age: Option<u32>,
+///   }
+///
+///   assert_eq!(FooBuilder::SCHEMA, "name(name: impl Into<String>)\nage(age: u32) (optional)\n");
+///   ```
+///
+/// - `cfg = "..."`: put all the generated code - the builder type, its impls and the `builder()`
+///   method - behind `#[cfg(...)]` with the given predicate, e.g. `cfg = "feature = \"builder\""`
+///   to only expose the builder when a feature is enabled.
+///
+/// - `remote = "..."`: derive a builder for a type defined elsewhere - e.g. in another crate - by
+///   deriving it on a local struct that mirrors its fields, like serde's remote derive. The build
+///   methods create the remote type with a struct literal, which requires its fields to be
+///   accessible, or with `build_via` - e.g. the remote type's constructor. `apply_method`,
+///   `merge_from_method`, `partial_type`, `constructor`, `invariant` and `reset` are not supported.
+///
+///   ```
+///   mod other_crate {
+///       pub struct Config {
+///           pub name: String,
+///           pub retries: u32,
+///       }
+///   }
+///
+///   use typed_builder::TypedBuilder;
+///
+///   #[derive(TypedBuilder)]
+///   #[builder(remote = "other_crate::Config")]
+///   struct ConfigDef {
+///       #[builder(setter(into))]
+///       name: String,
+///       #[builder(default = 3)]
+///       retries: u32,
+///   }
+///
+///   let config: other_crate::Config = ConfigDef::builder().name("server").build();
+///   assert_eq!(config.retries, 3);
+///   ```
 ///
 /// - `field_defaults(...)` is structured like the `#[builder(...)]` attribute you can put on the
 ///   fields and sets default options for fields of the type. If specific field need to revert some
@@ -122,6 +486,9 @@ use core::ops::FnOnce;
 ///    struct Point { x: f32, y: f32 }
 ///    ```
 ///
+/// - `into_all`: a shorthand for `field_defaults(setter(into))`. Specific fields can opt out with
+///   `#[builder(setter(!into))]`.
+///
 /// - `mutators(...)` takes functions, that can mutate fields inside of the builder.
 ///   See [mutators](#mutators) for details.
 ///
@@ -133,10 +500,38 @@ use core::ops::FnOnce;
 /// - `default = ...`: make the field optional, defaulting to the expression `...`.
 ///
 /// - `default_code = "..."`: make the field optional, defaulting to the expression `...`. Note that
-///    you need to enclose it in quotes, which allows you to use it together with other custom
-///    derive proc-macro crates that complain about "expected literal".
-///    Note that if `...` contains a string, you can use raw string literals to avoid escaping the
-///    double quotes - e.g. `#[builder(default_code = r#""default text".to_owned()"#)]`.
+///   you need to enclose it in quotes, which allows you to use it together with other custom
+///   derive proc-macro crates that complain about "expected literal".
+///   Note that if `...` contains a string, you can use raw string literals to avoid escaping the
+///   double quotes - e.g. `#[builder(default_code = r#""default text".to_owned()"#)]`.
+///
+/// - `default_once = ...`: make the field optional, defaulting to the expression `...` which is
+///   evaluated only once - the first time a builder needs it - and cloned for every later build.
+///   Useful for expensive defaults. This requires `std`, and the field type must be `Clone`, `Send`
+///   and `Sync` and must not depend on the generic parameters of the struct. The expression cannot
+///   refer to other fields, since its value is shared by all the builds.
+///
+/// - `default_try = ...`: make the field optional, defaulting to the expression `...` which returns
+///   a `Result` - the build method returns its error, converted with `?` into the error type of the
+///   build method. This requires a fallible build method, with `build_method(error = ...)` or
+///   `build_method(post = ...)`. Cannot be combined with `build_method(or_default)`,
+///   `apply_method(defaults)` and `recipe(capture_defaults)`, which resolve the defaults outside the
+///   build method, nor with the `lazy`, `each`, `entry` and `try_into(deferred)` setter settings.
+///
+/// - `computed = "..."`: the field is always initialized to the expression `...` (given as a
+///   string, like `default_code`) and no setter is generated for it. The expression can refer to
+///   earlier-defined fields. Unlike `setter(skip)`, this states that the field must never be set by
+///   the user, so it cannot be combined with `default`, `default_code`, `setter(...)`,
+///   `default_once`, `via_mutators` or `mutators`.
+///
+/// - `missing_message = "..."`: the message the compiler shows when building without setting this
+///   (required) field, instead of the default "Missing required field ...". It is reported as the
+///   error itself (through `#[diagnostic::on_unimplemented]`), while the default message is the
+///   note of a deprecation warning on the `build` method.
+///
+/// - `secret`: show the field as `***` in the `Debug` impl of the builder type (see
+///   `builder_type(derive(Debug))`) once it is set, instead of its value - e.g. for passwords and
+///   tokens. The type of the field does not need to implement `Debug`.
 ///
 /// - `via_mutators`: initialize the field when constructing the builder, useful in combination
 ///   with [mutators](#mutators).
@@ -156,18 +551,187 @@ use core::ops::FnOnce;
 ///   - `skip`: do not define a method on the builder for this field. This requires that a default
 ///     be set.
 ///
+///   - `lazy`: for `skip` fields of a lazily initialized type - like `std::sync::LazyLock<T>` or
+///     `std::cell::LazyCell<T>` - initialize the field with `Type::new(|| default)`, so the default
+///     is only computed on the first access of the built value's field. The closure cannot capture
+///     anything, so the default cannot refer to other fields.
+///
 ///   - `into`: automatically convert the argument of the setter method to the type of the field.
 ///     Note that this conversion interferes with Rust's type inference and integer literal
 ///     detection, so this may reduce ergonomics if the field type is generic or an unsigned integer.
 ///
+///   - `into(via = [Type1, Type2, ...])`: like `into`, but convert the argument through the listed
+///     intermediate types, in order, before converting it to the type of the field. This allows
+///     setting a field from a type that only converts into it indirectly.
+///
+///   - `into(generic = "S")`: like `into`, but the setter declares a named generic parameter -
+///     `fn field<S: Into<T>>(self, field: S)` - instead of taking `impl Into<T>`. This reads better
+///     in the docs, and allows specifying the argument type with a turbofish. The name cannot be
+///     one of the struct's type or const generic parameters, nor `__` followed by a field name,
+///     which the builder uses for its own generic parameters. Can be combined with `via`.
+///
 ///   - `strip_option`: for `Option<...>` fields only, this makes the setter wrap its argument with
 ///     `Some(...)`, relieving the caller from having to do this. Note that with this setting on
 ///     one cannot set the field to `None` with the setter - so the only way to get it to be `None`
 ///     is by using `#[builder(default)]` and not calling the field's setter.
 ///
+///   - `strip_option(ty = Type, some = path::to::Some, none = expr)`: like `strip_option`, but for
+///     a custom option-like type `Type<...>` instead of `Option<...>`. The setter takes the type
+///     inside it and wraps the argument with the `some` constructor. Unless the field has another
+///     default, `none` becomes its default.
+///
 ///   - `strip_bool`: for `bool` fields only, this makes the setter receive no arguments and simply
 ///     set the field's value to `true`. When used, the `default` is automatically set to `false`.
 ///
+///   - `boxed`: for `Box<dyn Trait>` fields only, this makes the setter accept any
+///     `impl Trait` and box it, relieving the caller from having to call `Box::new(...)`. The
+///     argument must be `'static` unless the trait object declares a lifetime bound, e.g.
+///     `Box<dyn Trait + 'a>`. Cannot be combined with `into`.
+///
+///   - `from_slice`: for `Vec<T>` fields only, this makes the setter accept a `&[T]` and clone
+///     its items into a new `Vec`. When combined with `into`, the setter accepts a slice of any
+///     `Clone` type that converts into `T`, and converts each item.
+///
+///   - `take`: make the setter accept a `&mut T` and take the value out of it with
+///     [`core::mem::take`], leaving `T::default()` in its place - so a large value the caller no
+///     longer needs is moved into the builder instead of cloned. Requires `T: Default`. Can be
+///     combined with `strip_option`, in which case the setter takes the type inside the `Option`.
+///
+///   - `from_fn`: for collection fields, this makes the setter accept a closure returning
+///     `Option<Item>`, and collect the items it returns until it returns `None` - like
+///     [`core::iter::from_fn`]. The item type is the collection's [`IntoIterator::Item`], so e.g. for
+///     maps the closure returns `Option<(K, V)>`.
+///
+///   - `setter_fn`: also generate an associated `<setter name>_setter()` function that returns the
+///     setter as a function value, e.g. `impl FnOnce(FooBuilder<...>, T) -> FooBuilder<...>`, for
+///     passing it to higher-order functions. The function value cannot take `impl Trait`
+///     arguments, so with `into` it takes the field's type (or the first `via` type) and with
+///     `from_slice` it takes a slice of the items' type. Cannot be combined with `transform` or
+///     `boxed`.
+///
+///   - `field_mut`: also generate a `<setter name>_mut(&mut self) -> &mut T` method, for editing
+///     the value in place after it was set - e.g. pushing to a `Vec` that was already passed to
+///     the setter. The method only exists on builders where the field is set, so calling it before
+///     the setter is a compile error.
+///
+///   - `requires_token = Type`: make the setters of the field take a value of the given type as
+///     their first argument, e.g. `.superuser(token)` or `.quota(token, 5)`. This gates the
+///     setters behind a capability token that only some callers can construct - typically a type
+///     with private fields. The token is dropped right away, and the type must be in scope where
+///     the struct is declared.
+///
+///   - `clamp(min = ..., max = ...)`: clamp the setter's argument into the given range instead of
+///     rejecting out-of-range values. Works for any [`Ord`] type and for `f32` and `f64`, using
+///     their `clamp` methods - which panic if `min > max`, and for floats leave `NaN` as is. The
+///     value is clamped after any conversion (`into`, `try_into`, ...), and with `strip_option`
+///     the type inside the `Option` is clamped.
+///
+///   - `maybe_into`: keep the setter taking the exact type, and also generate a
+///     `<setter name>_into` setter that accepts anything that converts into it with [`Into`]. This
+///     keeps type inference working for the regular setter - e.g. for integer literals - while still
+///     allowing conversions. Can be combined with `strip_option`, in which case both setters take
+///     the type inside the `Option`. It is an error if the `_into` setter's name is used by
+///     another setter.
+///
+///   - `unit = "..."`: also generate a `<setter name>_<unit>` setter that takes a quantity in that
+///     unit and passes it to `<field type>::from_<unit>`. The known units are those of
+///     [`Duration`](core::time::Duration) - `secs`, `millis`, `micros` and `nanos` take a `u64`,
+///     `secs_f32` and `secs_f64` take a float. Other units can be given as
+///     `unit(name = "...", ty = ..., via = ...)`, where `ty` is the type the setter takes and `via`
+///     is the function that converts it. Can be used several times, and combined with
+///     `strip_option`, in which case the converted value is wrapped in `Some`.
+///
+///     ```
+///     use std::time::Duration;
+///     use typed_builder::TypedBuilder;
+///
+///     fn from_mins(mins: u64) -> Duration {
+///         Duration::from_secs(mins * 60)
+///     }
+///
+///     #[derive(TypedBuilder)]
+///     struct Foo {
+///         #[builder(setter(unit = "secs", unit = "millis", unit(name = "mins", ty = u64, via = from_mins)))]
+///         timeout: Duration,
+///     }
+///
+///     assert_eq!(Foo::builder().timeout_millis(1500).build().timeout, Duration::from_millis(1500));
+///     assert_eq!(Foo::builder().timeout_mins(2).build().timeout, Duration::from_secs(120));
+///     ```
+///
+///   - `async_into`: make the setter an `async fn` that takes anything implementing
+///     [`IntoFuture`](core::future::IntoFuture) whose output is the field's type, and awaits it
+///     before storing the value - so the setter call needs an `.await`. Async and regular setters can
+///     be mixed freely in one chain. Can be combined with `strip_option` and `clamp`.
+///
+///   - `each = "..."` or `each(name = "...", into, max = N)`: for `Vec<...>` fields only, also
+///     generate a setter with the given name that adds a single item to the field, and can be
+///     called any number of times. It can follow the field's regular setter, adding to the value it
+///     set, but the regular setter cannot be called after items were added. The first item is added
+///     to the field's `default` (or to an empty `Vec` if it has none), so with `each` the default
+///     cannot refer to other fields. If the items are tuples, the setter takes each element of the
+///     tuple as a separate argument. With `into`, each
+///     argument is converted with [`Into`]. With `max` (at least 1), the `each` setter panics when
+///     the field already has `N` items - counting the items of its `default` - and the build method
+///     panics if a `Vec` with more than `N` items was given to the regular setter.
+///
+///   - `entry = "..."` or `entry(name = "...", into)`: for `HashMap<...>` and `BTreeMap<...>` fields
+///     only, also generate a setter with the given name that takes a key and a value and inserts
+///     them into the field, and can be called any number of times. Like `each`, it can follow the
+///     field's regular setter, but the regular setter cannot be called after entries were inserted,
+///     and the first entry is inserted into the field's `default` (which therefore cannot refer to
+///     other fields). A repeated key replaces the value. With `into`, the key and the value are
+///     converted with [`Into`].
+///
+///   - `indexed`: for `[T; N]` fields with a literal `N` of up to 32 only, also generate a setter
+///     per element, named after the field's setter with the index appended (`field_0`, `field_1`,
+///     ...), which sets that single element. The elements can be set in any order, each one once,
+///     and the field counts as set only after all of them were. Cannot be combined with `default`,
+///     `default_once`, `via_mutators` or `try_into(deferred)`.
+///
+///   - `merge`: allow calling the setter again after the field was set, extending the set value
+///     with the argument via [`Extend`] instead of being an error - e.g. for `HashMap` fields, the
+///     maps are merged and later keys overwrite earlier ones. Works for any field type that can be
+///     extended with itself. The first call sets the field like a regular setter, so it replaces
+///     the field's `default` rather than merging into it. Can be combined with `into`.
+///
+///   - `extend`: for collection fields, make the setter accept any [`IntoIterator`] of the
+///     collection's items, and allow calling it again to add more items via [`Extend`] - e.g. to
+///     assemble a `Vec` of arguments from several sources. Like with `merge`, the first call replaces
+///     the field's `default`.
+///
+///   - `format`: for `String` fields only, this makes the setter accept [`core::fmt::Arguments`], so
+///     that callers can pass `format_args!(...)` directly and the string is allocated only once.
+///     When combined with `into`, the field can be of any type that converts from `String`.
+///
+///   - `try_into`: make the setter accept any value that can be converted to the field's type with
+///     [`TryInto`], and return a `Result` - with the builder on success, and the conversion's error
+///     on failure. The other setters stay infallible, so `?` is only needed after the fallible ones.
+///     When several fallible setters have different error types, `?` converts each of them into the
+///     caller's error type via [`From`]. Can be combined with `strip_option`.
+///
+///   - `try_into(deferred)`: like `try_into`, but the setter stays infallible - the result of the
+///     conversion is stored in the builder, and its error is returned by the build method instead.
+///     This keeps long setter chains free of `?`. The build method then returns a `Result`, whose
+///     error type is set with `build_method(error = ...)` (or taken from `build_method(post = ...)`)
+///     and must implement `From` for the errors of all the deferred setters. Cannot be combined
+///     with `build_method(or_default)`, `apply_method`, `constructor` and `short_circuit`, nor with
+///     mutators that require the field.
+///
+///   - `parse`: make the setter accept a `&str` and parse it into the field's type with
+///     [`FromStr`](core::str::FromStr). Like `try_into`, the setter returns a `Result`, with the
+///     parse error on failure. Can be combined with `strip_option`.
+///
+///   - `convert = ...`: a single setting for the conversion of the setter's argument, that replaces
+///     any conversion set before it (e.g. in `field_defaults`). One of `into`, `try_into`, `parse`
+///     and `none`. `convert = into` is the same as `into`, and so on, while `convert = none` turns
+///     all these conversions off.
+///
+///   - `number_cast`: for primitive numeric fields only, this makes the setter accept any primitive
+///     number and convert it to the field's type with `as`. **Warning** - this conversion is lossy:
+///     it truncates, wraps and saturates exactly like `as` does, so only use it when that is the
+///     intended behavior. Can be combined with `strip_option`, but not with `into`.
+///
 ///   - `transform = |param1: Type1, param2: Type2 ...| expr`: this makes the setter accept
 ///     `param1: Type1, param2: Type2 ...` instead of the field type itself. The parameters are
 ///     transformed into the field type using the expression `expr`. The transformation is performed
@@ -181,6 +745,9 @@ use core::ops::FnOnce;
 ///     `suffix = "_value"` results in setters like `x_value` or `y_value`. This option is combinable
 ///     with `prefix = "..."`.
 ///
+///   - `name = ...` names the setter method, replacing the field's name and any `prefix` and
+///     `suffix` - e.g. `name = width` for the first field of a tuple struct.
+///
 ///   - `mutable_during_default_resolution`: when expressions in `default = ...` field attributes
 ///     are evaluated, this field will be mutable, allowing earlier-defined fields to be mutated by
 ///     later-defined fields.
@@ -192,11 +759,52 @@ use core::ops::FnOnce;
 ///
 /// Fields annotated with `#[builder(via_mutators)]` are always available to mutators. Additional fields,
 /// that the mutator accesses need to be delcared using `#[mutator(requires = [field1, field2, ...])]`.
-/// The mutator will only be availible to call when they are set.
+/// The mutator will only be availible to call when they are set. The fields can be referred to either
+/// by their names or by the names of their setters.
 ///
 /// Mutators on a field, result in them automatically making the field required, i.e., it needs to be
 /// marked as `via_mutators`, or its setter be called. Appart from that, they behave identically.
 ///
+/// Mutators run when they are called, so they always run before `.build()` resolves the defaults.
+/// A field that has a default but was not set is therefore not available to mutators - requiring it
+/// makes the mutator callable only after the field's setter was called.
+///
+/// For tooling and documentation, the builder type has a `MUTATOR_REQUIREMENTS` constant - a
+/// `&[(&str, &[&str])]` that lists each mutator that can be called on the builder with the names
+/// of the fields it requires, in declaration order. Fields marked as `via_mutators` are always
+/// available, so they are not listed. The constant is only generated when there are mutators.
+///
+/// A mutator marked with `#[mutator(auto)]` is not exposed as a builder method. Instead, it runs
+/// automatically in `.build()` (and `.build_or_default()`), after the defaults were resolved, so it
+/// has access to all the fields. Automatic mutators cannot take arguments, and run in declaration
+/// order - first the ones declared on fields, then the ones declared on the struct.
+///
+/// With `#[builder(mutators_run = before_defaults)]`, the automatic mutators run before the fields
+/// with a default are resolved instead, so that the default expressions see the mutated values. The
+/// mutators then only have access to the fields without a default - a mutator that reads a
+/// defaulted field needs `mutators_run = after_defaults`, which is the default.
+///
+/// ```
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(PartialEq, Debug, TypedBuilder)]
+/// #[builder(mutators(
+///     #[mutator(auto)]
+///     fn normalize(&mut self) {
+///         self.name = self.name.trim().to_lowercase();
+///     }
+/// ))]
+/// struct Struct {
+///     name: String,
+///     #[builder(default)]
+///     tags: Vec<String>,
+/// }
+///
+/// assert_eq!(
+///     Struct::builder().name("  Foo ".to_owned()).build(),
+///     Struct { name: "foo".to_owned(), tags: vec![] });
+/// ```
+///
 /// ```
 /// use typed_builder::TypedBuilder;
 ///
@@ -234,23 +842,215 @@ use core::ops::FnOnce;
 /// ```
 pub use typed_builder_macro::TypedBuilder;
 
+/// `#[builder]` on a free function replaces it with a function of the same name that returns a
+/// typed builder for its parameters. The setters are named after the parameters, and the build
+/// method, `call()`, calls the original function with them:
+///
+/// ```
+/// #[typed_builder::builder]
+/// fn send_request(
+///     #[builder(setter(into))] url: String,
+///     #[builder(default = 3)] retries: u32,
+///     timeout_secs: u64,
+/// ) -> String {
+///     format!("GET {} ({} retries, {}s timeout)", url, retries, timeout_secs)
+/// }
+///
+/// assert_eq!(
+///     send_request().url("example.com").timeout_secs(5).call(),
+///     "GET example.com (3 retries, 5s timeout)",
+/// );
+/// ```
+///
+/// The parameters take the same `#[builder(...)]` attributes as the fields of a struct deriving
+/// [`TypedBuilder`], and the arguments of `#[builder(...)]` itself are those of the attribute on the
+/// struct - e.g. `#[builder(build_method(name = send))]`. The builder type is named after the
+/// function in `PascalCase`, e.g. `SendRequestBuilder`. The settings that generate items on the
+/// struct itself - `apply_method`, `merge_from_method`, `partial_type`, `constructor`, `invariant`,
+/// `reset` - as well as `build_via` and `short_circuit` are not supported.
+///
+/// Methods, `async` functions and `impl Trait` parameters are not supported, and like struct
+/// fields, reference parameters need named lifetimes.
+///
+/// Parameters can be conditional with `#[cfg(...)]`, like struct fields. Since the macro cannot
+/// evaluate the conditions, it generates the builder once for every combination of them, so a
+/// function can have at most four distinct conditions on its parameters.
+///
+/// On an `impl` block, `#[builder]` generates a builder for each associated function marked with
+/// `#[builder]` or `#[builder(...)]` - e.g. a `new` constructor that checks invariants. The
+/// functions stay as they are, and the builder's `build()` calls them. The builder of `new` is
+/// created with `Foo::builder()` and named `FooBuilder`, and that of any other function `with_x`
+/// with `Foo::with_x_builder()` and named `FooWithXBuilder`. The arguments of `#[builder(...)]` on
+/// the `impl` block apply to all of them, before those on each function:
+///
+/// ```
+/// #[derive(Debug, PartialEq)]
+/// struct Temperature {
+///     kelvin: f64,
+/// }
+///
+/// #[typed_builder::builder]
+/// impl Temperature {
+///     #[builder]
+///     fn new(kelvin: f64) -> Self {
+///         assert!(kelvin >= 0.0, "below absolute zero");
+///         Self { kelvin }
+///     }
+///
+///     #[builder(build_method(name = try_build))]
+///     fn from_celsius(celsius: f64) -> Result<Self, String> {
+///         if celsius < -273.15 {
+///             return Err(format!("{} is below absolute zero", celsius));
+///         }
+///         Ok(Self { kelvin: celsius + 273.15 })
+///     }
+/// }
+///
+/// assert_eq!(Temperature::builder().kelvin(1.0).build(), Temperature { kelvin: 1.0 });
+/// assert!(Temperature::from_celsius_builder().celsius(-300.0).try_build().is_err());
+/// ```
+///
+/// `Self` in the parameter and return types of the functions refers to the type of the `impl`
+/// block. Generic associated functions and trait impls are not supported.
+///
+/// Prefer writing the path - `#[typed_builder::builder]` - to importing the macro: an imported
+/// `builder` is ambiguous with the `#[builder(...)]` attributes of `#[derive(TypedBuilder)]`.
+pub use typed_builder_macro::builder;
+
+/// `#[typed_builder_alias(Alias = Foo<...>)]` on a generic struct deriving [`TypedBuilder`]
+/// declares a type alias of an instance of it, together with an alias of its builder type named
+/// after it - `AliasBuilder`. Like the builder type itself, the builder alias defaults to a fresh
+/// builder, so it can be named without spelling out the state of its fields:
+///
+/// ```
+/// use typed_builder::{typed_builder_alias, TypedBuilder};
+///
+/// #[typed_builder_alias(Port = Setting<u16>)]
+/// #[derive(TypedBuilder)]
+/// struct Setting<T> {
+///     value: T,
+///     #[builder(default)]
+///     fallbacks: Vec<T>,
+/// }
+///
+/// fn default_port() -> PortBuilder {
+///     Port::builder()
+/// }
+///
+/// let port: Port = default_port().value(8080).build();
+/// assert_eq!(port.value, 8080);
+/// ```
+///
+/// The alias can take generic parameters of its own - `#[typed_builder_alias(Named<'a> = Foo<'a,
+/// u32>)]` - and generic arguments the alias leaves out get the defaults of the struct's generic
+/// parameters. The attribute must be placed above `#[derive(TypedBuilder)]`, and the aliased type
+/// must be an instance of the struct it is on:
+///
+/// ```compile_fail
+/// use typed_builder::{typed_builder_alias, TypedBuilder};
+///
+/// #[derive(TypedBuilder)]
+/// struct Other<T> {
+///     value: T,
+/// }
+///
+/// #[typed_builder_alias(Port = Other<u16>)]
+/// #[derive(TypedBuilder)]
+/// struct Setting<T> {
+///     value: T,
+/// }
+/// ```
+pub use typed_builder_macro::typed_builder_alias;
+
 #[doc(hidden)]
 pub trait Optional<T> {
     fn into_value<F: FnOnce() -> T>(self, default: F) -> T;
+
+    fn into_option(self) -> Option<T>;
+
+    fn as_option(&self) -> Option<&T>;
 }
 
 impl<T> Optional<T> for () {
     fn into_value<F: FnOnce() -> T>(self, default: F) -> T {
         default()
     }
+
+    fn into_option(self) -> Option<T> {
+        None
+    }
+
+    fn as_option(&self) -> Option<&T> {
+        None
+    }
 }
 
 impl<T> Optional<T> for (T,) {
     fn into_value<F: FnOnce() -> T>(self, _: F) -> T {
         self.0
     }
+
+    fn into_option(self) -> Option<T> {
+        Some(self.0)
+    }
+
+    fn as_option(&self) -> Option<&T> {
+        Some(&self.0)
+    }
+}
+
+/// The state of an array field with `setter(indexed)` while only some of its elements are set -
+/// a tuple with a `()` or a `(T,)` per element.
+#[doc(hidden)]
+#[derive(Clone, Debug)]
+pub struct Indexed<S>(pub S);
+
+#[doc(hidden)]
+pub enum IndexAllSet {}
+
+#[doc(hidden)]
+pub enum IndexUnset {}
+
+/// Chained over the element states of an `Indexed` field, resolves to `IndexAllSet` only if all the
+/// elements are set.
+#[doc(hidden)]
+pub trait IndexSlot {
+    type Then<Rest>;
+}
+
+impl IndexSlot for () {
+    type Then<Rest> = IndexUnset;
+}
+
+impl<T> IndexSlot for (T,) {
+    type Then<Rest> = Rest;
+}
+
+#[doc(hidden)]
+pub trait NumberCast<T> {
+    fn number_cast(self) -> T;
+}
+
+macro_rules! impl_number_cast {
+    ($($from:ty),*) => {
+        $(
+            impl_number_cast!(@from $from => u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+        )*
+    };
+    (@from $from:ty => $($to:ty),*) => {
+        $(
+            impl NumberCast<$to> for $from {
+                #[allow(clippy::unnecessary_cast)]
+                fn number_cast(self) -> $to {
+                    self as $to
+                }
+            }
+        )*
+    };
 }
 
+impl_number_cast!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
 // It'd be nice for the compilation tests to live in tests/ with the rest, but short of pulling in
 // some other test runner for that purpose (e.g. compiletest_rs), rustdoc compile_fail in this
 // crate is all we can use.
@@ -332,6 +1132,251 @@ impl<T> Optional<T> for (T,) {
 /// let _ = Foo::builder().x(Uncloneable).clone();
 /// ```
 ///
+/// A computed field has no setter:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(PartialEq, TypedBuilder)]
+/// struct Foo {
+///     x: i32,
+///     #[builder(computed = "x * 2")]
+///     y: i32,
+/// }
+///
+/// let _ = Foo::builder().x(1).y(2).build();
+/// ```
+///
+/// And it cannot be combined with setter settings:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(PartialEq, TypedBuilder)]
+/// struct Foo {
+///     #[builder(computed = "2", setter(into))]
+///     y: i32,
+/// }
+/// ```
+///
+/// A union builder can only set a single member:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(Clone, Copy, TypedBuilder)]
+/// union Foo {
+///     x: i32,
+///     y: f32,
+/// }
+///
+/// let _ = Foo::builder().x(1).y(2.0).build();
+/// ```
+///
+/// And it cannot be built without setting one:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(Clone, Copy, TypedBuilder)]
+/// union Foo {
+///     x: i32,
+///     y: f32,
+/// }
+///
+/// let _ = Foo::builder().build();
+/// ```
+///
+/// Union members cannot have settings that need a default or repeated calls:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(Clone, Copy, TypedBuilder)]
+/// union Foo {
+///     #[builder(setter(field_mut))]
+///     x: i32,
+///     y: f32,
+/// }
+/// ```
+///
+/// `field_mut` accessors are only available after the field was set:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(setter(field_mut))]
+///     x: Vec<i32>,
+/// }
+///
+/// let mut builder = Foo::builder();
+/// builder.x_mut().push(1);
+/// ```
+///
+/// Building without a required field, with a custom message:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(missing_message = "you must call `.x()`")]
+///     x: i32,
+/// }
+///
+/// let _ = Foo::builder().build();
+/// ```
+///
+/// `number_cast` only works on primitive numeric fields:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(setter(number_cast))]
+///     x: String,
+/// }
+/// ```
+///
+/// Unknown parameters are rejected, with a suggestion when they look like a misspelled one:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(defalt)]
+///     x: i32,
+/// }
+/// ```
+///
+/// The `_into` setter of `maybe_into` cannot collide with another field's setter:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(setter(maybe_into))]
+///     x: String,
+///     x_into: String,
+/// }
+/// ```
+///
+/// Builder method parameters must be named after fields:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(builder_method(params(identifier: u32)))]
+/// struct Foo {
+///     id: u32,
+/// }
+/// ```
+///
+/// Builder traits from `cfg_derive(...)` are not implemented when the `cfg` predicate is false:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(builder_type(cfg_derive(any(), PartialEq)))]
+/// struct Foo {
+///     x: i32,
+/// }
+///
+/// assert!(Foo::builder().x(1) == Foo::builder().x(1));
+/// ```
+///
+/// Nothing is generated when the `cfg` predicate is false - not even the `builder()` method:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(cfg = "any()")]
+/// struct Foo {
+///     x: i32,
+/// }
+///
+/// Foo::builder();
+/// ```
+///
+/// A name in a mutator's `requires` that is both a field name and another field's setter name is
+/// ambiguous:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(mutators(
+///     #[mutator(requires = [with_x])]
+///     fn touch(&mut self) {}
+/// ))]
+/// struct Foo {
+///     #[builder(setter(prefix = "with_"))]
+///     x: i32,
+///     #[builder(via_mutators)]
+///     with_x: i32,
+/// }
+/// ```
+///
+/// The named generic of an `into` setter cannot collide with the struct's generics:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo<T> {
+///     #[builder(setter(into(generic = "T")))]
+///     x: T,
+/// }
+/// ```
+///
+/// `drop_order` requires the fields to be declared in the order they should be dropped:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(drop_order = [guard, resource])]
+/// struct Foo {
+///     resource: String,
+///     guard: String,
+/// }
+/// ```
+///
+/// Structs with more fields than `max_fields` are rejected:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(max_fields = 2)]
+/// struct Foo {
+///     x: i32,
+///     y: i32,
+///     z: i32,
+/// }
+/// ```
+///
+/// A constructor must take all the fields without a default:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(constructor(new(x)))]
+/// struct Foo {
+///     x: i32,
+///     y: i32,
+/// }
+/// ```
+///
 /// Handling deprecated fields:
 ///
 /// ```compile_fail
@@ -0,0 +1,15 @@
+use typed_builder::TypedBuilder;
+
+#[derive(TypedBuilder)]
+#[builder = "default"]
+struct Foo {
+    x: i32,
+}
+
+#[derive(TypedBuilder)]
+struct Bar {
+    #[builder = "default"]
+    x: i32,
+}
+
+fn main() {}
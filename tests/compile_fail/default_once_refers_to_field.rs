@@ -0,0 +1,10 @@
+use typed_builder::TypedBuilder;
+
+#[derive(TypedBuilder)]
+struct Foo {
+    x: i32,
+    #[builder(default_once = x * 10)]
+    y: i32,
+}
+
+fn main() {}
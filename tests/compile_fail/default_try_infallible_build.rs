@@ -0,0 +1,9 @@
+use typed_builder::TypedBuilder;
+
+#[derive(TypedBuilder)]
+struct Foo {
+    #[builder(default_try = "80".parse())]
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use typed_builder::TypedBuilder;
+
+#[derive(TypedBuilder)]
+struct Foo {
+    len: usize,
+    #[builder(default = vec![0; len], setter(each = "item"))]
+    items: Vec<i32>,
+}
+
+fn main() {}
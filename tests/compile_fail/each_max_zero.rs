@@ -0,0 +1,9 @@
+use typed_builder::TypedBuilder;
+
+#[derive(TypedBuilder)]
+struct Foo {
+    #[builder(default, setter(each(name = "item", max = 0)))]
+    items: Vec<i32>,
+}
+
+fn main() {}
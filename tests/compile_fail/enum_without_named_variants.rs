@@ -0,0 +1,9 @@
+use typed_builder::TypedBuilder;
+
+#[derive(TypedBuilder)]
+enum Shape {
+    Point,
+    Circle(f64),
+}
+
+fn main() {}
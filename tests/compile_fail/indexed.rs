@@ -0,0 +1,17 @@
+use typed_builder::TypedBuilder;
+
+#[derive(TypedBuilder)]
+struct TooLong {
+    #[builder(setter(indexed))]
+    x: [u8; 33],
+}
+
+#[derive(TypedBuilder)]
+struct NotSet {
+    #[builder(setter(indexed))]
+    x: [u8; 2],
+}
+
+fn main() {
+    let _ = NotSet::builder().x_0(1).build();
+}
@@ -0,0 +1,22 @@
+use typed_builder::TypedBuilder;
+
+#[derive(TypedBuilder)]
+struct ConstParam<const N: usize> {
+    #[builder(setter(into(generic = "N")))]
+    x: String,
+}
+
+#[derive(TypedBuilder)]
+struct BuilderParam {
+    #[builder(setter(into(generic = "__y")))]
+    x: String,
+    y: i32,
+}
+
+#[derive(TypedBuilder)]
+struct Lifetime<'a> {
+    #[builder(setter(into(generic = "'a")))]
+    x: &'a str,
+}
+
+fn main() {}
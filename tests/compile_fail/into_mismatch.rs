@@ -0,0 +1,13 @@
+use typed_builder::TypedBuilder;
+
+struct NotAString;
+
+#[derive(TypedBuilder)]
+struct Foo {
+    #[builder(setter(into))]
+    name: String,
+}
+
+fn main() {
+    let _ = Foo::builder().name(NotAString).build();
+}
@@ -0,0 +1,11 @@
+use typed_builder::TypedBuilder;
+
+#[derive(TypedBuilder)]
+struct Foo {
+    #[builder(missing_message = "you must call `.x()`")]
+    x: i32,
+}
+
+fn main() {
+    let _ = Foo::builder().build();
+}
@@ -0,0 +1,21 @@
+use typed_builder::TypedBuilder;
+
+#[derive(TypedBuilder)]
+struct Fallible {
+    #[builder(setter(try_into, parse))]
+    x: i32,
+}
+
+#[derive(TypedBuilder)]
+struct Take {
+    #[builder(setter(take, format))]
+    x: String,
+}
+
+#[derive(TypedBuilder)]
+struct Merge {
+    #[builder(setter(merge, strip_option))]
+    x: Option<Vec<i32>>,
+}
+
+fn main() {}
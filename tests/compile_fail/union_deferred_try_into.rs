@@ -0,0 +1,11 @@
+use typed_builder::TypedBuilder;
+
+#[derive(TypedBuilder)]
+#[builder(build_method(error = std::num::TryFromIntError))]
+union Number {
+    #[builder(setter(try_into(deferred)))]
+    small: u8,
+    large: u64,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use typed_builder::TypedBuilder;
+
+#[derive(TypedBuilder)]
+struct Foo {
+    #[builder(defalt)]
+    x: i32,
+}
+
+fn main() {}
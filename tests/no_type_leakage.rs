@@ -12,6 +12,7 @@ struct Foo {
     x: i32,
 }
 
+#[allow(dead_code)]
 pub struct Bar(Foo);
 
 impl Bar {
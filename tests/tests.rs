@@ -67,6 +67,67 @@ fn test_generics() {
     assert!(Foo::builder().x(1).y(2).build() == Foo { x: 1, y: 2 });
 }
 
+#[test]
+fn test_generics_inferred_from_setters() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Wrapper<T, U> {
+        value: T,
+        #[builder(default)]
+        others: Vec<U>,
+    }
+
+    // The builder does not fix the generic parameters - the setters do, even across statements.
+    let builder = Wrapper::<_, _>::builder();
+    let builder = builder.value(42_u32);
+    let wrapper = builder.others(vec!["foo"]).build();
+    assert_eq!(
+        wrapper,
+        Wrapper {
+            value: 42_u32,
+            others: vec!["foo"],
+        }
+    );
+}
+
+#[test]
+fn test_type_alias() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo<T> {
+        x: T,
+        #[builder(default)]
+        y: Vec<T>,
+    }
+
+    type Alias = Foo<u32>;
+    type AliasBuilder = FooBuilder<u32>;
+
+    // No turbofish needed - the alias fixes `T` even when nothing else would.
+    assert_eq!(Alias::builder().x(Default::default()).build(), Foo { x: 0, y: vec![] });
+    let _: AliasBuilder = Alias::builder();
+}
+
+#[test]
+fn test_typed_builder_alias() {
+    #[typed_builder::typed_builder_alias(Alias = Foo<u32>)]
+    #[typed_builder::typed_builder_alias(Borrowed<'a> = Foo<u8, &'a str>)]
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo<T, U = Vec<T>> {
+        x: T,
+        #[builder(via_mutators)]
+        u: Option<U>,
+    }
+
+    fn fresh_builder() -> AliasBuilder {
+        Alias::builder()
+    }
+
+    assert_eq!(fresh_builder().x(Default::default()).build(), Foo { x: 0, u: None });
+    let builder: AliasBuilder<((u32,), (Option<Vec<u32>>,))> = fresh_builder().x(1);
+    assert_eq!(builder.build(), Alias { x: 1, u: None });
+    let borrowed: Borrowed = Borrowed::builder().x(2).build();
+    assert_eq!(borrowed, Foo { x: 2, u: None });
+}
+
 #[test]
 fn test_2d_const_generics() {
     #[derive(PartialEq, TypedBuilder)]
@@ -121,6 +182,43 @@ fn test_into() {
     assert!(Foo::builder().x(1_u8).build() == Foo { x: 1 });
 }
 
+#[test]
+fn test_into_via() {
+    #[derive(PartialEq, Debug)]
+    struct Meters(f64);
+
+    #[derive(PartialEq, Debug)]
+    struct Distance(Meters);
+
+    impl From<f64> for Meters {
+        fn from(value: f64) -> Self {
+            Self(value)
+        }
+    }
+
+    impl From<Meters> for Distance {
+        fn from(value: Meters) -> Self {
+            Self(value)
+        }
+    }
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(into(via = [Meters])))]
+        x: Distance,
+        #[builder(setter(into(via = [f64, Meters]), strip_option))]
+        y: Option<Distance>,
+    }
+
+    assert_eq!(
+        Foo::builder().x(1.5).y(2.5f32).build(),
+        Foo {
+            x: Distance(Meters(1.5)),
+            y: Some(Distance(Meters(2.5)))
+        }
+    );
+}
+
 #[test]
 fn test_strip_option_with_into() {
     #[derive(PartialEq, TypedBuilder)]
@@ -143,6 +241,42 @@ fn test_into_with_strip_option() {
     assert!(Foo::builder().x(1_u8).build() == Foo { x: Some(1) });
 }
 
+#[test]
+fn test_boxed() {
+    trait Greet {
+        fn greet(&self) -> String;
+    }
+
+    struct English;
+
+    impl Greet for English {
+        fn greet(&self) -> String {
+            "hello".to_owned()
+        }
+    }
+
+    struct Named<'a>(&'a str);
+
+    impl Greet for Named<'_> {
+        fn greet(&self) -> String {
+            format!("hello {}", self.0)
+        }
+    }
+
+    #[derive(TypedBuilder)]
+    struct Foo<'a> {
+        #[builder(setter(boxed))]
+        x: Box<dyn Greet>,
+        #[builder(setter(boxed))]
+        y: Box<dyn Greet + 'a>,
+    }
+
+    let name = "world".to_owned();
+    let foo = Foo::builder().x(English).y(Named(&name)).build();
+    assert_eq!(foo.x.greet(), "hello");
+    assert_eq!(foo.y.greet(), "hello world");
+}
+
 #[test]
 fn test_strip_bool() {
     #[derive(PartialEq, TypedBuilder)]
@@ -280,6 +414,28 @@ fn test_skip() {
     assert!(Foo::builder().y(1_u8).build() == Foo { x: 0, y: 1, z: 2 });
 }
 
+#[test]
+fn test_computed() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(field_defaults(setter(into)))]
+    struct Foo {
+        x: i32,
+        #[builder(computed = "x * 2")]
+        y: i32,
+        #[builder(computed = r#"format!("{x}-{y}")"#)]
+        z: String,
+    }
+
+    assert_eq!(
+        Foo::builder().x(3).build(),
+        Foo {
+            x: 3,
+            y: 6,
+            z: "3-6".to_owned()
+        }
+    );
+}
+
 #[test]
 fn test_docs() {
     #[derive(TypedBuilder)]
@@ -427,6 +583,22 @@ fn test_builder_type_with_default_on_generic_type() {
     );
 }
 
+#[test]
+fn test_builder_method_with_default_type_parameter() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo<T: Default = u32> {
+        #[builder(default)]
+        x: T,
+    }
+
+    // The builder type keeps the default of the type parameter, so neither needs a turbofish.
+    let foo: Foo = Foo::builder().build();
+    assert_eq!(foo, Foo { x: 0 });
+    let builder: FooBuilder = Foo::builder();
+    assert_eq!(builder.x(5).build(), Foo { x: 5 });
+    assert_eq!(<Foo>::builder().build(), Foo { x: 0 });
+}
+
 #[test]
 fn test_builder_type_skip_into() {
     #[derive(PartialEq, TypedBuilder)]
@@ -460,6 +632,29 @@ fn test_default_code() {
     );
 }
 
+#[test]
+fn test_default_once() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static EVALUATIONS: AtomicUsize = AtomicUsize::new(0);
+
+    fn expensive() -> Vec<String> {
+        EVALUATIONS.fetch_add(1, Ordering::SeqCst);
+        vec!["a".to_owned(), "b".to_owned()]
+    }
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo {
+        #[builder(default_once = expensive())]
+        x: Vec<String>,
+    }
+
+    assert_eq!(Foo::builder().build().x, ["a", "b"]);
+    assert_eq!(Foo::builder().build().x, ["a", "b"]);
+    assert_eq!(Foo::builder().x(vec![]).build().x, Vec::<String>::new());
+    assert_eq!(EVALUATIONS.load(Ordering::SeqCst), 1);
+}
+
 #[test]
 fn test_field_defaults_default_value() {
     #[derive(PartialEq, TypedBuilder)]
@@ -531,6 +726,7 @@ fn test_clone_builder() {
 #[test]
 #[allow(clippy::items_after_statements)]
 fn test_clone_builder_with_generics() {
+    #[allow(dead_code)]
     #[derive(PartialEq, Default)]
     struct Uncloneable;
 
@@ -746,127 +942,2396 @@ fn test_into_angle_bracket_type() {
 }
 
 #[test]
-fn test_into_set_generic_impl_into() {
-    #[derive(TypedBuilder)]
-    #[builder(build_method(into))]
-    struct Foo {
-        value: i32,
-    }
+fn test_build_method_returns_impl() {
+    mod shapes {
+        use typed_builder::TypedBuilder;
 
-    #[derive(Debug, PartialEq)]
-    struct Bar {
-        value: i32,
-    }
+        pub trait Shape {
+            fn area(&self) -> i32;
+        }
 
-    impl From<Foo> for Bar {
-        fn from(val: Foo) -> Self {
-            Self { value: val.value }
+        #[derive(TypedBuilder)]
+        #[builder(builder_type(vis = "pub"), build_method(returns_impl = Shape + Send))]
+        struct Rect {
+            width: i32,
+            height: i32,
+        }
+
+        impl Shape for Rect {
+            fn area(&self) -> i32 {
+                self.width * self.height
+            }
+        }
+
+        pub fn rect() -> RectBuilder {
+            Rect::builder()
         }
     }
 
-    let bar: Bar = Foo::builder().value(42).build();
-    assert_eq!(bar, Bar { value: 42 });
+    use shapes::Shape;
+
+    fn assert_send(_: &impl Send) {}
+
+    let rect = shapes::rect().width(2).height(3).build();
+    assert_send(&rect);
+    assert_eq!(rect.area(), 6);
 }
 
 #[test]
-fn test_prefix() {
-    #[derive(Debug, PartialEq, TypedBuilder)]
-    #[builder(field_defaults(setter(prefix = "with_")))]
-    struct Foo {
-        x: i32,
-        y: i32,
+fn test_apply_method() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(apply_method(name = update, vis = ""))]
+    struct Foo<T> {
+        x: T,
+        #[builder(default, setter(strip_option))]
+        y: Option<i32>,
+        #[builder(default = 3, setter(skip))]
+        z: i32,
+        #[builder(via_mutators = 4)]
+        w: i32,
     }
 
-    let foo = Foo::builder().with_x(1).with_y(2).build();
-    assert_eq!(foo, Foo { x: 1, y: 2 });
+    let mut foo = Foo {
+        x: "a",
+        y: Some(1),
+        z: 1,
+        w: 1,
+    };
+    Foo::builder().x("b").update(&mut foo);
+    assert_eq!(
+        foo,
+        Foo {
+            x: "b",
+            y: Some(1),
+            z: 1,
+            w: 4
+        }
+    );
+    Foo::builder().y(5).update(&mut foo);
+    assert_eq!(
+        foo,
+        Foo {
+            x: "b",
+            y: Some(5),
+            z: 1,
+            w: 4
+        }
+    );
 }
 
 #[test]
-fn test_suffix() {
-    #[derive(Debug, PartialEq, TypedBuilder)]
-    #[builder(field_defaults(setter(suffix = "_value")))]
+fn test_apply_method_defaults() {
+    // `Some` is shadowed to make sure the generated code does not rely on the prelude.
+    #[allow(dead_code)]
+    struct Some;
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(apply_method)]
     struct Foo {
         x: i32,
+        #[builder(default = 2)]
         y: i32,
     }
 
-    let foo = Foo::builder().x_value(1).y_value(2).build();
-    assert_eq!(foo, Foo { x: 1, y: 2 });
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(apply_method(defaults))]
+    struct Bar {
+        x: i32,
+        #[builder(default = 2)]
+        y: i32,
+        #[builder(default = y * 10)]
+        z: i32,
+        #[builder(default = 4, setter(skip))]
+        w: i32,
+    }
+
+    let mut foo = Foo { x: 10, y: 20 };
+    Foo::builder().x(1).apply_to(&mut foo);
+    assert_eq!(foo, Foo { x: 1, y: 20 });
+
+    let mut bar = Bar {
+        x: 10,
+        y: 20,
+        z: 30,
+        w: 40,
+    };
+    Bar::builder().apply_to(&mut bar);
+    assert_eq!(
+        bar,
+        Bar {
+            x: 10,
+            y: 2,
+            z: 20,
+            w: 4
+        }
+    );
+    Bar::builder().x(1).y(3).apply_to(&mut bar);
+    assert_eq!(bar, Bar { x: 1, y: 3, z: 30, w: 4 });
 }
 
 #[test]
-fn test_prefix_and_suffix() {
-    #[derive(Debug, PartialEq, TypedBuilder)]
-    #[builder(field_defaults(setter(prefix = "with_", suffix = "_value")))]
+fn test_into_strip_option_default_combinations() {
+    // `Some` is shadowed to make sure the generated code does not rely on the prelude.
+    #[allow(dead_code)]
+    struct Some;
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
     struct Foo {
-        x: i32,
+        plain: Option<String>,
+        #[builder(setter(into))]
+        into: Option<String>,
+        #[builder(setter(strip_option))]
+        strip_option: Option<String>,
+        #[builder(setter(into, strip_option))]
+        into_strip_option: Option<String>,
+    }
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct FooWithDefaults {
+        #[builder(default)]
+        plain: Option<String>,
+        #[builder(default, setter(into))]
+        into: Option<String>,
+        #[builder(default, setter(strip_option))]
+        strip_option: Option<String>,
+        #[builder(default, setter(into, strip_option))]
+        into_strip_option: Option<String>,
+    }
+
+    let expected = Foo {
+        plain: Option::Some("a".to_owned()),
+        into: Option::Some("b".to_owned()),
+        strip_option: Option::Some("c".to_owned()),
+        into_strip_option: Option::Some("d".to_owned()),
+    };
+    assert_eq!(
+        Foo::builder()
+            .plain(Option::Some("a".to_owned()))
+            .into(Option::Some("b".to_owned()))
+            .strip_option("c".to_owned())
+            .into_strip_option("d")
+            .build(),
+        expected,
+    );
+
+    assert_eq!(
+        FooWithDefaults::builder().build(),
+        FooWithDefaults {
+            plain: None,
+            into: None,
+            strip_option: None,
+            into_strip_option: None,
+        },
+    );
+    assert_eq!(
+        FooWithDefaults::builder()
+            .plain(Option::Some("a".to_owned()))
+            .into(Option::Some("b".to_owned()))
+            .strip_option("c".to_owned())
+            .into_strip_option("d")
+            .build(),
+        FooWithDefaults {
+            plain: expected.plain,
+            into: expected.into,
+            strip_option: expected.strip_option,
+            into_strip_option: expected.into_strip_option,
+        },
+    );
+}
+
+#[test]
+fn test_builder_type_impl_trait() {
+    mod sealed {
+        pub trait Sealed {}
+    }
+
+    trait FooBuilderMarker: sealed::Sealed {}
+    impl<T: sealed::Sealed + OtherMarker> FooBuilderMarker for T {}
+
+    trait OtherMarker {}
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(builder_type(impl_trait = sealed::Sealed, impl_trait = OtherMarker))]
+    struct Foo<T> {
+        x: T,
+        #[builder(default)]
         y: i32,
     }
 
-    let foo = Foo::builder().with_x_value(1).with_y_value(2).build();
-    assert_eq!(foo, Foo { x: 1, y: 2 });
+    fn is_marked<B: FooBuilderMarker>(builder: B) -> B {
+        builder
+    }
+
+    let builder = is_marked(Foo::builder());
+    let builder = is_marked(builder.x(1));
+    assert_eq!(is_marked(builder.y(2)).build(), Foo { x: 1, y: 2 });
 }
 
 #[test]
-fn test_issue_118() {
-    #[derive(TypedBuilder)]
-    #[builder(build_method(into=Bar))]
+fn test_field_mut() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(field_defaults(setter(field_mut)))]
     struct Foo<T> {
-        #[builder(default, setter(skip))]
-        #[allow(dead_code)]
-        foo: Option<T>,
+        x: Vec<T>,
+        #[builder(default, setter(prefix = "with_"))]
+        y: String,
+        #[builder(setter(!field_mut))]
+        z: i32,
     }
 
-    struct Bar;
+    let mut builder = Foo::builder().z(3).x(vec![1]);
+    builder.x_mut().push(2);
+    let mut builder = builder.with_y("a".to_owned());
+    builder.with_y_mut().push('b');
+    builder.x_mut().push(3);
+    assert_eq!(
+        builder.build(),
+        Foo {
+            x: vec![1, 2, 3],
+            y: "ab".to_owned(),
+            z: 3
+        }
+    );
+}
 
-    impl<T> From<Foo<T>> for Bar {
-        fn from(_value: Foo<T>) -> Self {
-            Self
+#[test]
+fn test_from_slice() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(from_slice))]
+        x: Vec<i32>,
+        #[builder(setter(from_slice, into))]
+        y: Vec<String>,
+    }
+
+    assert_eq!(
+        Foo::builder().x(&[1, 2, 3]).y(&["a", "b"]).build(),
+        Foo {
+            x: vec![1, 2, 3],
+            y: vec!["a".to_owned(), "b".to_owned()],
         }
+    );
+
+    let items = [4, 5];
+    assert_eq!(
+        Foo::builder().x(&items[1..]).y(&[] as &[&str]).build(),
+        Foo { x: vec![5], y: vec![] }
+    );
+}
+
+#[test]
+fn test_into_all() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(into_all)]
+    struct Foo {
+        x: String,
+        #[builder(default, setter(strip_option))]
+        y: Option<String>,
+        #[builder(setter(!into))]
+        z: Vec<u8>,
     }
 
-    let _ = Foo::<u32>::builder().build();
+    assert_eq!(
+        Foo::builder().x("a").y("b").z(Vec::new()).build(),
+        Foo {
+            x: "a".to_owned(),
+            y: Some("b".to_owned()),
+            z: Vec::new(),
+        }
+    );
+    // Without `into`, the type of the empty `Vec` is inferred from the field.
+    assert_eq!(Foo::builder().x('c').z(vec![]).build().z, Vec::<u8>::new());
 }
 
 #[test]
-fn test_mutable_defaults() {
-    #[derive(TypedBuilder, PartialEq, Debug)]
+fn test_builder_type_derive() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(builder_type(derive(Clone, Debug, PartialEq)))]
+    struct Foo<T> {
+        x: T,
+        #[builder(default)]
+        y: String,
+        #[builder(default, setter(skip))]
+        z: i32,
+        #[builder(via_mutators)]
+        r#w: i32,
+    }
+
+    let builder = Foo::builder().x(1);
+    assert_eq!(format!("{builder:?}"), "FooBuilder { x: 1, w: 0 }");
+    assert_eq!(builder.clone(), builder);
+    assert_ne!(builder, Foo::builder().x(2));
+
+    let builder = builder.y("a".to_owned());
+    assert_eq!(format!("{builder:?}"), "FooBuilder { x: 1, y: \"a\", w: 0 }");
+    assert_eq!(builder.clone(), builder);
+}
+
+#[test]
+fn test_setter_fn() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(field_defaults(setter(setter_fn)))]
     struct Foo {
-        #[builder(default, mutable_during_default_resolution, setter(strip_option))]
-        x: Option<i32>,
-        #[builder(default = if let Some(x) = x.as_mut() {
-            *x *= 2;
-            *x
-        } else {
-            Default::default()
-        })]
-        y: i32,
+        x: i32,
+        #[builder(setter(into))]
+        y: String,
+        #[builder(setter(strip_bool))]
+        z: bool,
     }
 
-    let foo = Foo::builder().x(5).build();
+    fn apply<B, T, R>(builder: B, setter: impl FnOnce(B, T) -> R, value: T) -> R {
+        setter(builder, value)
+    }
 
-    assert_eq!(foo, Foo { x: Some(10), y: 10 });
+    let builder = apply(Foo::builder(), FooBuilder::x_setter(), 1);
+    let builder = apply(builder, FooBuilder::y_setter(), "a".to_owned());
+    let builder = FooBuilder::z_setter()(builder);
+    assert_eq!(
+        builder.build(),
+        Foo {
+            x: 1,
+            y: "a".to_owned(),
+            z: true,
+        }
+    );
 }
 
 #[test]
-fn test_preinitialized_fields() {
-    #[derive(Debug, PartialEq, TypedBuilder)]
+fn test_build_or_default() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(build_method(or_default))]
     struct Foo {
         x: i32,
-        #[builder(via_mutators)]
-        y: i32,
-        #[builder(via_mutators = 2)]
+        y: String,
+        #[builder(default = x + 1)]
         z: i32,
-        #[builder(via_mutators(init = 2))]
-        w: i32,
     }
 
-    let foo = Foo::builder().x(1).build();
-    assert_eq!(foo, Foo { x: 1, y: 0, z: 2, w: 2 });
+    assert_eq!(
+        Foo::builder().build_or_default(),
+        Foo {
+            x: 0,
+            y: String::new(),
+            z: 1
+        }
+    );
+    assert_eq!(
+        Foo::builder().x(5).build_or_default(),
+        Foo {
+            x: 5,
+            y: String::new(),
+            z: 6
+        }
+    );
+    assert_eq!(
+        Foo::builder().y("a".to_owned()).z(2).build_or_default(),
+        Foo {
+            x: 0,
+            y: "a".to_owned(),
+            z: 2
+        }
+    );
 }
 
 #[test]
-fn test_mutators_item() {
+fn test_number_cast() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(number_cast))]
+        x: u8,
+        #[builder(setter(number_cast))]
+        y: f32,
+        #[builder(default, setter(number_cast, strip_option))]
+        z: Option<i64>,
+    }
+
+    assert_eq!(
+        Foo::builder().x(3_i32).y(2_u64).z(1.9_f64).build(),
+        Foo {
+            x: 3,
+            y: 2.0,
+            z: Some(1)
+        }
+    );
+    // The conversion is lossy, exactly like `as`.
+    assert_eq!(Foo::builder().x(300_i32).y(1).build().x, 44);
+}
+
+#[test]
+fn test_try_into() {
+    use core::num::TryFromIntError;
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(try_into))]
+        x: u8,
+        y: i32,
+        #[builder(default, setter(try_into, strip_option))]
+        z: Option<u16>,
+    }
+
+    fn build(x: i64, z: u32) -> Result<Foo, TryFromIntError> {
+        Ok(Foo::builder().x(x)?.y(2).z(z)?.build())
+    }
+
+    assert_eq!(build(1, 3), Ok(Foo { x: 1, y: 2, z: Some(3) }));
+    assert!(build(256, 3).is_err());
+    assert!(build(1, 65536).is_err());
+
+    // Infallible conversions still go through `Result`.
+    let builder = Foo::builder().y(1).x(5_u8);
+    assert_eq!(builder.unwrap().build(), Foo { x: 5, y: 1, z: None });
+}
+
+#[test]
+fn test_try_into_deferred() {
+    use core::num::TryFromIntError;
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(build_method(error = TryFromIntError))]
+    struct Foo {
+        #[builder(setter(try_into(deferred)))]
+        x: u8,
+        y: i32,
+        #[builder(default, setter(try_into(deferred), strip_option))]
+        z: Option<u16>,
+    }
+
+    // The error type of `post` is used when there is one.
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(build_method(post = |bar: Bar| -> Result<Bar, Box<dyn std::error::Error>> {
+        if bar.x.is_ascii_digit() { Err("digit".into()) } else { Ok(bar) }
+    }))]
+    struct Bar {
+        #[builder(setter(try_into(deferred)))]
+        x: char,
+    }
+
+    assert_eq!(
+        Foo::builder().x(1_i64).y(2).z(3_u32).build(),
+        Ok(Foo { x: 1, y: 2, z: Some(3) })
+    );
+    assert_eq!(Foo::builder().y(2).x(1_u8).build(), Ok(Foo { x: 1, y: 2, z: None }));
+    assert!(Foo::builder().x(256_i64).y(2).build().is_err());
+    assert!(Foo::builder().x(1_i64).y(2).z(65536_u32).build().is_err());
+
+    assert_eq!(Bar::builder().x(97_u32).build().unwrap(), Bar { x: 'a' });
+    assert_eq!(Bar::builder().x(48_u32).build().unwrap_err().to_string(), "digit");
+    assert!(Bar::builder().x(0xD800_u32).build().is_err());
+}
+
+#[test]
+fn test_default_try() {
+    use core::num::ParseIntError;
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(build_method(error = ParseIntError))]
+    struct Foo {
+        text: &'static str,
+        #[builder(default_try = text.parse())]
+        port: u16,
+        #[builder(default_try = "8".parse(), setter(skip))]
+        workers: u8,
+    }
+
+    // The errors are converted into the error type of `post`.
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(build_method(post = |bar: Bar| -> Result<Bar, Box<dyn std::error::Error>> { Ok(bar) }))]
+    struct Bar {
+        #[builder(default_try = "x".parse::<i32>())]
+        x: i32,
+    }
+
+    assert_eq!(
+        Foo::builder().text("80").build(),
+        Ok(Foo {
+            text: "80",
+            port: 80,
+            workers: 8
+        })
+    );
+    assert!(Foo::builder().text("http").build().is_err());
+    assert_eq!(
+        Foo::builder().text("http").port(80).build(),
+        Ok(Foo {
+            text: "http",
+            port: 80,
+            workers: 8
+        })
+    );
+
+    assert!(Bar::builder().build().is_err());
+    assert_eq!(Bar::builder().x(1).build().unwrap(), Bar { x: 1 });
+}
+
+#[test]
+fn test_merge_from_method() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(merge_from_method)]
+    struct Foo {
+        x: i32,
+        #[builder(default)]
+        y: Option<String>,
+        #[builder(default)]
+        z: Vec<i32>,
+        #[builder(default)]
+        map: std::collections::HashMap<&'static str, i32>,
+        #[builder(default)]
+        set: std::collections::BTreeSet<i32>,
+    }
+
+    let mut foo = Foo::builder()
+        .x(1)
+        .y(Some("a".to_owned()))
+        .z(vec![1])
+        .map([("a", 1), ("b", 2)].into())
+        .set([1, 2].into())
+        .build();
+    foo.merge_from(
+        &Foo::builder()
+            .x(0)
+            .y(Some("b".to_owned()))
+            .z(vec![2, 3])
+            .map([("b", 3), ("c", 4)].into())
+            .set([2, 3].into())
+            .build(),
+    );
+    assert_eq!(
+        foo,
+        Foo {
+            x: 1,
+            y: Some("b".to_owned()),
+            z: vec![1, 2, 3],
+            map: [("a", 1), ("b", 3), ("c", 4)].into(),
+            set: [1, 2, 3].into(),
+        }
+    );
+    foo.merge_from(&Foo::builder().x(2).build());
+    assert_eq!(
+        foo,
+        Foo {
+            x: 2,
+            y: Some("b".to_owned()),
+            z: vec![1, 2, 3],
+            map: [("a", 1), ("b", 3), ("c", 4)].into(),
+            set: [1, 2, 3].into(),
+        }
+    );
+}
+
+#[test]
+fn test_default_option_of_non_default_type() {
+    #[derive(PartialEq, Debug)]
+    struct NotDefault(i32);
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo<T> {
+        #[builder(default)]
+        x: Option<NotDefault>,
+        #[builder(default, setter(strip_option))]
+        y: Option<T>,
+    }
+
+    assert_eq!(Foo::<NotDefault>::builder().build(), Foo { x: None, y: None });
+    assert_eq!(
+        Foo::builder().x(Some(NotDefault(1))).y(NotDefault(2)).build(),
+        Foo {
+            x: Some(NotDefault(1)),
+            y: Some(NotDefault(2)),
+        }
+    );
+}
+
+#[test]
+fn test_format() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(format))]
+        x: String,
+        #[builder(setter(format, into))]
+        y: Box<str>,
+    }
+
+    let name = "world";
+    assert_eq!(
+        Foo::builder()
+            .x(format_args!("hello {name}"))
+            .y(format_args!("{}-{}", 1, 2))
+            .build(),
+        Foo {
+            x: "hello world".to_owned(),
+            y: "1-2".into(),
+        }
+    );
+}
+
+#[test]
+fn test_recipe() {
+    use core::sync::atomic::{AtomicU32, Ordering};
+
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(recipe)]
+    struct Foo<T> {
+        x: T,
+        #[builder(default = COUNTER.fetch_add(1, Ordering::SeqCst))]
+        id: u32,
+    }
+
+    let recipe: FooRecipe<_, _> = Foo::builder().x("a".to_owned()).into_recipe();
+    let foos = [recipe.build(), recipe.clone().build(), recipe.build()];
+    assert_eq!(
+        foos,
+        [
+            Foo {
+                x: "a".to_owned(),
+                id: 0
+            },
+            Foo {
+                x: "a".to_owned(),
+                id: 1
+            },
+            Foo {
+                x: "a".to_owned(),
+                id: 2
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_recipe_capture_defaults() {
+    use core::sync::atomic::{AtomicU32, Ordering};
+
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(recipe(capture_defaults))]
+    struct Foo {
+        x: u32,
+        #[builder(default = x + COUNTER.fetch_add(1, Ordering::SeqCst))]
+        id: u32,
+        #[builder(default = id * 2, setter(skip))]
+        double_id: u32,
+    }
+
+    let recipe = Foo::builder().x(10).into_recipe();
+    assert_eq!(COUNTER.load(Ordering::SeqCst), 1);
+    let foos = [recipe.build(), recipe.clone().build()];
+    assert_eq!(foos.map(|foo| (foo.id, foo.double_id)), [(10, 20), (10, 20)]);
+    assert_eq!(COUNTER.load(Ordering::SeqCst), 1);
+
+    let recipe = Foo::builder().x(10).id(3).into_recipe();
+    assert_eq!(
+        recipe.build(),
+        Foo {
+            x: 10,
+            id: 3,
+            double_id: 6
+        }
+    );
+    assert_eq!(COUNTER.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_convert() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(field_defaults(setter(into)))]
+    struct Foo {
+        a: String,
+        #[builder(setter(convert = into))]
+        b: String,
+        #[builder(setter(convert = try_into))]
+        c: u8,
+        #[builder(setter(convert = parse))]
+        d: u16,
+        #[builder(default, setter(convert = parse, strip_option))]
+        e: Option<f32>,
+        #[builder(setter(convert = none))]
+        f: Vec<u8>,
+    }
+
+    let foo = Foo::builder()
+        .a("a")
+        .b("b")
+        .c(3_i64)
+        .unwrap()
+        .d("4")
+        .unwrap()
+        .e("5.5")
+        .unwrap()
+        .f(vec![])
+        .build();
+    assert_eq!(
+        foo,
+        Foo {
+            a: "a".to_owned(),
+            b: "b".to_owned(),
+            c: 3,
+            d: 4,
+            e: Some(5.5),
+            f: vec![],
+        }
+    );
+
+    assert!(Foo::builder().c(256).is_err());
+    assert!(Foo::builder().d("x").is_err());
+    assert!(Foo::builder().e("").is_err());
+}
+
+#[test]
+fn test_partial_type() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(partial_type(name = FooOverrides, derive(Debug, Default, Clone)))]
+    struct Foo<T> {
+        x: T,
+        #[builder(default)]
+        y: String,
+        #[builder(default, setter(skip))]
+        z: i32,
+    }
+
+    let overrides = FooOverrides {
+        y: Some("b".to_owned()),
+        z: Some(3),
+        ..Default::default()
+    };
+    let foo = Foo::builder().x(1).y("a".to_owned()).build();
+    assert_eq!(
+        foo.with_overrides(overrides.clone()),
+        Foo {
+            x: 1,
+            y: "b".to_owned(),
+            z: 3
+        }
+    );
+    assert_eq!(
+        Foo::builder().x(1).build().with_overrides(FooOverrides::default()),
+        Foo {
+            x: 1,
+            y: String::new(),
+            z: 0
+        }
+    );
+}
+
+#[test]
+fn test_each() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Request {
+        #[builder(default, setter(each(name = "header", into)))]
+        headers: Vec<(String, String)>,
+        #[builder(setter(each = "arg"))]
+        args: Vec<u8>,
+        #[builder(default, setter(each = "triple"))]
+        triples: Vec<(i32, i32, i32)>,
+    }
+
+    assert_eq!(
+        Request::builder()
+            .header("Accept", "text/plain")
+            .arg(1)
+            .header("Host", "example.com")
+            .arg(2)
+            .build(),
+        Request {
+            headers: vec![
+                ("Accept".to_owned(), "text/plain".to_owned()),
+                ("Host".to_owned(), "example.com".to_owned()),
+            ],
+            args: vec![1, 2],
+            triples: vec![],
+        }
+    );
+
+    // The regular setter can still be used, and the `each` setter adds to it:
+    assert_eq!(
+        Request::builder().args(vec![1]).arg(2).triple(1, 2, 3).build(),
+        Request {
+            headers: vec![],
+            args: vec![1, 2],
+            triples: vec![(1, 2, 3)],
+        }
+    );
+}
+
+#[test]
+fn test_each_adds_to_default() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo {
+        #[builder(default = vec![1, 2], setter(each = "item"))]
+        items: Vec<i32>,
+    }
+
+    assert_eq!(Foo::builder().build(), Foo { items: vec![1, 2] });
+    assert_eq!(Foo::builder().item(3).item(4).build(), Foo { items: vec![1, 2, 3, 4] });
+    // The regular setter replaces the default.
+    assert_eq!(Foo::builder().items(vec![5]).item(6).build(), Foo { items: vec![5, 6] });
+}
+
+#[test]
+fn test_into_vec_from_array() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(into))]
+        x: Vec<i32>,
+        #[builder(default, setter(into, strip_option))]
+        y: Option<Vec<String>>,
+    }
+
+    assert_eq!(
+        Foo::builder().x([1, 2, 3]).y(["a".to_owned()]).build(),
+        Foo {
+            x: vec![1, 2, 3],
+            y: Some(vec!["a".to_owned()]),
+        }
+    );
+    assert_eq!(Foo::builder().x([]).build().x, Vec::<i32>::new());
+}
+
+#[test]
+fn test_context() {
+    #[derive(Clone)]
+    struct Ctx {
+        prefix: &'static str,
+        base: u32,
+    }
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(context = Ctx, mutators(
+        fn bump(&mut self) {
+            self.counter += self.context().base;
+        }
+    ))]
+    struct Foo {
+        #[builder(default = format!("{}-name", context.prefix))]
+        name: String,
+        #[builder(default = context.base * 2)]
+        doubled: u32,
+        #[builder(via_mutators)]
+        counter: u32,
+    }
+
+    let ctx = Ctx { prefix: "ctx", base: 21 };
+    assert_eq!(
+        Foo::builder(ctx.clone()).bump().build(),
+        Foo {
+            name: "ctx-name".to_owned(),
+            doubled: 42,
+            counter: 21,
+        }
+    );
+    assert_eq!(
+        Foo::builder(ctx).name("explicit".to_owned()).bump().bump().build(),
+        Foo {
+            name: "explicit".to_owned(),
+            doubled: 42,
+            counter: 42,
+        }
+    );
+}
+
+#[test]
+fn test_maybe_into() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(maybe_into))]
+        x: String,
+        #[builder(default, setter(maybe_into, strip_option))]
+        y: Option<u64>,
+    }
+
+    assert_eq!(
+        Foo::builder().x(String::from("exact")).y(1).build(),
+        Foo {
+            x: "exact".to_owned(),
+            y: Some(1),
+        }
+    );
+    assert_eq!(
+        Foo::builder().x_into("converted").y_into(2u8).build(),
+        Foo {
+            x: "converted".to_owned(),
+            y: Some(2),
+        }
+    );
+}
+
+#[test]
+fn test_builder_method_params() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(builder_method(name = with, params(id: u32, name: String)))]
+    struct Foo {
+        id: u32,
+        name: String,
+        #[builder(default = format!("{name}#{id}"))]
+        label: String,
+    }
+
+    assert_eq!(
+        Foo::with(42, "foo".to_owned()).build(),
+        Foo {
+            id: 42,
+            name: "foo".to_owned(),
+            label: "foo#42".to_owned(),
+        }
+    );
+    assert_eq!(
+        Foo::with(1, "bar".to_owned()).label("custom".to_owned()).build().label,
+        "custom"
+    );
+}
+
+#[test]
+fn test_builder_type_debug_skip() {
+    // `Some` is shadowed to make sure the generated code does not rely on the prelude.
+    #[allow(dead_code)]
+    struct Some;
+
+    // Deliberately not `Debug`, since skipped fields don't need to be.
+    #[allow(dead_code)]
+    struct Secret(&'static str);
+
+    #[derive(TypedBuilder)]
+    #[builder(builder_type(debug(skip = [password, token])))]
+    #[allow(dead_code)]
+    struct Foo {
+        user: &'static str,
+        password: Secret,
+        token: Secret,
+    }
+
+    let builder = Foo::builder().user("admin").password(Secret("hunter2")).token(Secret("abc"));
+    assert_eq!(format!("{builder:?}"), r#"FooBuilder { user: "admin" }"#);
+}
+
+#[test]
+fn test_secret_field() {
+    #[derive(TypedBuilder)]
+    #[builder(builder_type(derive(Debug)))]
+    #[allow(dead_code)]
+    struct Foo {
+        user: &'static str,
+        #[builder(secret)]
+        password: String,
+    }
+
+    let builder = Foo::builder().user("admin");
+    assert_eq!(format!("{builder:?}"), r#"FooBuilder { user: "admin" }"#);
+    let builder = builder.password("hunter2".to_owned());
+    let formatted = format!("{builder:?}");
+    assert!(!formatted.contains("hunter2"));
+    assert_eq!(formatted, r#"FooBuilder { user: "admin", password: *** }"#);
+}
+
+#[test]
+fn test_build_method_pinned() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(build_method(pinned))]
+    struct Foo {
+        x: i32,
+        #[builder(default = x + 1)]
+        y: i32,
+    }
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(build_method(pinned, into = String))]
+    struct Bar {
+        x: i32,
+    }
+
+    impl From<Bar> for String {
+        fn from(bar: Bar) -> Self {
+            bar.x.to_string()
+        }
+    }
+
+    let foo: core::pin::Pin<Box<Foo>> = Foo::builder().x(1).build();
+    assert_eq!(*foo, Foo { x: 1, y: 2 });
+    assert_eq!(*Bar::builder().x(3).build(), "3");
+}
+
+#[test]
+fn test_clamp() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(clamp(min = 0, max = 100)))]
+        percent: i32,
+        #[builder(setter(clamp(min = 0.0, max = 1.0)))]
+        ratio: f64,
+        #[builder(default, setter(strip_option, into, clamp(min = 1, max = 10)))]
+        level: Option<u32>,
+    }
+
+    assert_eq!(
+        Foo::builder().percent(150).ratio(-0.5).level(0u8).build(),
+        Foo {
+            percent: 100,
+            ratio: 0.0,
+            level: Some(1),
+        }
+    );
+    assert_eq!(Foo::builder().percent(-3).ratio(0.5).build().percent, 0);
+    assert!(Foo::builder().percent(50).ratio(f64::NAN).build().ratio.is_nan());
+}
+
+#[test]
+fn test_requires_token() {
+    mod admin {
+        pub struct AdminToken(());
+
+        pub fn login() -> AdminToken {
+            AdminToken(())
+        }
+    }
+    use admin::AdminToken;
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo {
+        name: &'static str,
+        #[builder(setter(requires_token = AdminToken, strip_bool))]
+        superuser: bool,
+        #[builder(default, setter(requires_token = AdminToken, into))]
+        quota: u64,
+    }
+
+    assert_eq!(
+        Foo::builder()
+            .name("root")
+            .superuser(admin::login())
+            .quota(admin::login(), 5u8)
+            .build(),
+        Foo {
+            name: "root",
+            superuser: true,
+            quota: 5,
+        }
+    );
+}
+
+#[test]
+fn test_builder_type_cfg_derive() {
+    #[derive(TypedBuilder)]
+    #[builder(builder_type(cfg_derive(test, Debug), cfg_derive(all(), PartialEq)))]
+    struct Foo {
+        #[allow(dead_code)]
+        x: i32,
+    }
+
+    let builder = Foo::builder().x(1);
+    assert_eq!(format!("{builder:?}"), "FooBuilder { x: 1 }");
+    assert!(builder == Foo::builder().x(1));
+}
+
+#[test]
+fn test_mutator_requires_setter_name() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(mutators(
+        #[mutator(requires = [with_x])]
+        fn double_x(&mut self) {
+            self.x *= 2;
+        }
+    ))]
+    struct Foo {
+        #[builder(setter(prefix = "with_"))]
+        x: i32,
+    }
+
+    assert_eq!(Foo::builder().with_x(2).double_x().build(), Foo { x: 4 });
+}
+
+#[test]
+fn test_merge() {
+    use std::collections::HashMap;
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(merge))]
+        map: HashMap<&'static str, i32>,
+        #[builder(default = HashMap::from([("default", 0)]), setter(merge, into))]
+        with_default: HashMap<&'static str, i32>,
+    }
+
+    let foo = Foo::builder()
+        .map(HashMap::from([("a", 1), ("b", 2)]))
+        .map(HashMap::from([("b", 3), ("c", 4)]))
+        .build();
+    assert_eq!(foo.map, HashMap::from([("a", 1), ("b", 3), ("c", 4)]));
+    assert_eq!(foo.with_default, HashMap::from([("default", 0)]));
+
+    // Setting the field replaces the default - later calls merge into the set value.
+    let foo = Foo::builder()
+        .map(HashMap::new())
+        .with_default([("a", 1)])
+        .with_default([("a", 2)])
+        .build();
+    assert_eq!(foo.with_default, HashMap::from([("a", 2)]));
+}
+
+#[test]
+fn test_merge_with_value_producing_setters() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(merge, from_slice))]
+        slices: Vec<u8>,
+        #[builder(setter(merge, from_fn))]
+        generated: Vec<u8>,
+        #[builder(setter(merge, transform = |x: u8| vec![x; 2]))]
+        doubled: Vec<u8>,
+    }
+
+    let mut next = [1, 2].into_iter();
+    assert_eq!(
+        Foo::builder()
+            .slices(&[1])
+            .slices(&[2, 3])
+            .generated(|| next.next())
+            .generated(|| None)
+            .doubled(1)
+            .doubled(2)
+            .build(),
+        Foo {
+            slices: vec![1, 2, 3],
+            generated: vec![1, 2],
+            doubled: vec![1, 1, 2, 2],
+        }
+    );
+}
+
+#[test]
+fn test_extend() {
+    use std::collections::BTreeMap;
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Command {
+        #[builder(setter(extend))]
+        args: Vec<String>,
+        #[builder(default, setter(extend))]
+        env: BTreeMap<&'static str, &'static str>,
+    }
+
+    let command = Command::builder()
+        .args(["--verbose".to_owned()])
+        .env(Some(("A", "1")))
+        .args(vec!["foo".to_owned(), "bar".to_owned()])
+        .env([("B", "2"), ("A", "3")])
+        .build();
+    assert_eq!(
+        command,
+        Command {
+            args: vec!["--verbose".to_owned(), "foo".to_owned(), "bar".to_owned()],
+            env: BTreeMap::from([("A", "3"), ("B", "2")]),
+        }
+    );
+    assert_eq!(Command::builder().args(None).build().env, BTreeMap::new());
+}
+
+#[test]
+fn test_entry() {
+    use std::collections::{BTreeMap, HashMap};
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Request {
+        #[builder(default, setter(entry = "header"))]
+        headers: HashMap<String, String>,
+        #[builder(setter(entry(name = "query", into)))]
+        params: BTreeMap<String, i32>,
+    }
+
+    let request = Request::builder()
+        .query("a", 1)
+        .header("Accept".to_owned(), "*/*".to_owned())
+        .query("b", 2)
+        .query("a", 3)
+        .build();
+    assert_eq!(
+        request,
+        Request {
+            headers: HashMap::from([("Accept".to_owned(), "*/*".to_owned())]),
+            params: BTreeMap::from([("a".to_owned(), 3), ("b".to_owned(), 2)]),
+        }
+    );
+
+    // The regular setter can be called first, and the entry setter adds to the set map.
+    let request = Request::builder()
+        .params(BTreeMap::from([("a".to_owned(), 1)]))
+        .query("b", 2)
+        .build();
+    assert_eq!(request.params, BTreeMap::from([("a".to_owned(), 1), ("b".to_owned(), 2)]));
+}
+
+#[test]
+fn test_entry_inserts_into_default() {
+    use std::collections::BTreeMap;
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo {
+        #[builder(default = BTreeMap::from([("a", 1), ("b", 2)]), setter(entry = "entry"))]
+        map: BTreeMap<&'static str, i32>,
+    }
+
+    assert_eq!(Foo::builder().build().map, BTreeMap::from([("a", 1), ("b", 2)]));
+    assert_eq!(
+        Foo::builder().entry("b", 3).entry("c", 4).build().map,
+        BTreeMap::from([("a", 1), ("b", 3), ("c", 4)])
+    );
+    // The regular setter replaces the default.
+    assert_eq!(
+        Foo::builder().map(BTreeMap::new()).entry("c", 4).build().map,
+        BTreeMap::from([("c", 4)])
+    );
+}
+
+#[test]
+fn test_indexed() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo<T> {
+        #[builder(setter(indexed))]
+        rgb: [u8; 3],
+        #[builder(setter(indexed))]
+        pair: [T; 2],
+    }
+
+    assert_eq!(
+        Foo::builder().rgb_0(1).pair_1("b").rgb_1(2).pair_0("a").rgb_2(3).build(),
+        Foo {
+            rgb: [1, 2, 3],
+            pair: ["a", "b"],
+        }
+    );
+
+    // The indices can be set in any order, and the regular setter still sets the whole array.
+    assert_eq!(
+        Foo::builder().rgb_2(3).rgb_0(1).rgb_1(2).pair([4, 5]).build(),
+        Foo {
+            rgb: [1, 2, 3],
+            pair: [4, 5],
+        }
+    );
+}
+
+#[test]
+fn test_into_named_generic() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo<T> {
+        #[builder(setter(into(generic = "S")))]
+        name: String,
+        #[builder(default, setter(into(generic = "V"), strip_option))]
+        value: Option<T>,
+    }
+
+    // The named generic parameters can be specified explicitly.
+    assert_eq!(
+        Foo::builder().name::<&str>("foo").value::<u8>(1).build(),
+        Foo {
+            name: "foo".to_owned(),
+            value: Some(1u32),
+        }
+    );
+}
+
+#[test]
+fn test_drop_order() {
+    use std::cell::RefCell;
+
+    struct Noisy<'a>(&'static str, &'a RefCell<Vec<&'static str>>);
+
+    impl Drop for Noisy<'_> {
+        fn drop(&mut self) {
+            self.1.borrow_mut().push(self.0);
+        }
+    }
+
+    #[derive(TypedBuilder)]
+    #[builder(drop_order = [guard, resource])]
+    #[allow(dead_code)]
+    struct Foo<'a> {
+        guard: Noisy<'a>,
+        other: i32,
+        resource: Noisy<'a>,
+    }
+
+    let dropped = RefCell::new(Vec::new());
+    drop(
+        Foo::builder()
+            .resource(Noisy("resource", &dropped))
+            .other(1)
+            .guard(Noisy("guard", &dropped))
+            .build(),
+    );
+    assert_eq!(*dropped.borrow(), ["guard", "resource"]);
+}
+
+#[test]
+fn test_max_fields() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(max_fields = 3)]
+    struct Foo {
+        x: i32,
+        y: i32,
+        #[builder(default)]
+        z: i32,
+    }
+
+    assert_eq!(Foo::builder().x(1).y(2).build(), Foo { x: 1, y: 2, z: 0 });
+}
+
+#[test]
+fn test_auto_mutators() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(build_method(or_default), mutators(
+        #[mutator(auto)]
+        fn double(&mut self) {
+            self.x *= 2;
+            self.log.push("double");
+        }
+    ))]
+    struct Foo {
+        #[builder(mutators(
+            #[mutator(auto)]
+            fn inc(&mut self) {
+                self.x += 1;
+                self.log.push("inc");
+            }
+        ))]
+        x: i32,
+        #[builder(default)]
+        log: Vec<&'static str>,
+    }
+
+    assert_eq!(
+        Foo::builder().x(1).build(),
+        Foo {
+            x: 4,
+            log: vec!["inc", "double"]
+        }
+    );
+    assert_eq!(
+        Foo::builder().build_or_default(),
+        Foo {
+            x: 2,
+            log: vec!["inc", "double"]
+        }
+    );
+}
+
+#[test]
+fn test_async_into() {
+    use core::future::Future;
+    use core::pin::pin;
+    use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        const VTABLE: RawWakerVTable = RawWakerVTable::new(|_| RawWaker::new(core::ptr::null(), &VTABLE), |_| {}, |_| {}, |_| {});
+        let waker = unsafe { Waker::from_raw(RawWaker::new(core::ptr::null(), &VTABLE)) };
+        let mut context = Context::from_waker(&waker);
+        let mut future = pin!(future);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    fn resolve(handle: u32) -> impl Future<Output = String> {
+        core::future::ready(format!("resource-{handle}"))
+    }
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo {
+        x: i32,
+        #[builder(setter(async_into))]
+        name: String,
+        #[builder(default, setter(async_into, strip_option))]
+        alias: Option<String>,
+    }
+
+    assert_eq!(
+        block_on(async { Foo::builder().x(1).name(resolve(2)).await.build() }),
+        Foo {
+            x: 1,
+            name: "resource-2".to_owned(),
+            alias: None
+        }
+    );
+    assert_eq!(
+        block_on(async {
+            Foo::builder()
+                .alias(async { "alias".to_owned() })
+                .await
+                .name(resolve(3))
+                .await
+                .x(2)
+                .build()
+        }),
+        Foo {
+            x: 2,
+            name: "resource-3".to_owned(),
+            alias: Some("alias".to_owned())
+        }
+    );
+}
+
+#[test]
+fn test_builder_type_derive_eq_hash() {
+    use std::collections::HashSet;
+
+    #[derive(TypedBuilder)]
+    #[builder(builder_type(derive(PartialEq, Eq, Hash)))]
+    #[allow(dead_code)]
+    struct Foo {
+        x: i32,
+        #[builder(default)]
+        y: String,
+    }
+
+    let mut builders = HashSet::new();
+    assert!(builders.insert(Foo::builder().x(1).y("a".to_owned())));
+    assert!(builders.insert(Foo::builder().x(2).y("a".to_owned())));
+    assert!(!builders.insert(Foo::builder().x(1).y("a".to_owned())));
+    assert_eq!(builders.len(), 2);
+
+    // The type-state is hashed too, so an unset field is distinct from a set one.
+    let mut partial_builders = HashSet::new();
+    assert!(partial_builders.insert(Foo::builder().x(1)));
+    assert!(!partial_builders.insert(Foo::builder().x(1)));
+    assert!(partial_builders.contains(&Foo::builder().x(1)));
+}
+
+#[test]
+fn test_constructor() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(constructor(new(x, y), with_z(z, y, x)))]
+    struct Foo {
+        x: i32,
+        #[builder(setter(into))]
+        y: String,
+        #[builder(default = x * 2)]
+        z: i32,
+        #[builder(default, setter(strip_option))]
+        w: Option<i32>,
+    }
+
+    assert_eq!(
+        Foo::new(1, "a".to_owned()),
+        Foo {
+            x: 1,
+            y: "a".to_owned(),
+            z: 2,
+            w: None
+        }
+    );
+    assert_eq!(
+        Foo::with_z(5, "b".to_owned(), 1),
+        Foo {
+            x: 1,
+            y: "b".to_owned(),
+            z: 5,
+            w: None
+        }
+    );
+}
+
+#[test]
+fn test_invariant() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(invariant = self.min <= self.max, invariant = !self.name.is_empty())]
+    struct Range {
+        min: i32,
+        max: i32,
+        #[builder(default = "range".to_owned())]
+        name: String,
+    }
+
+    assert_eq!(
+        Range::builder().min(1).max(2).build(),
+        Range {
+            min: 1,
+            max: 2,
+            name: "range".to_owned()
+        }
+    );
+
+    let result = std::panic::catch_unwind(|| Range::builder().min(2).max(1).build());
+    assert_eq!(result.is_err(), cfg!(debug_assertions));
+
+    let result = std::panic::catch_unwind(|| Range::builder().min(1).max(2).name(String::new()).build());
+    assert_eq!(result.is_err(), cfg!(debug_assertions));
+}
+
+#[test]
+fn test_build_via() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(build_via = Foo::__new)]
+    struct Foo {
+        x: i32,
+        #[builder(default = 2)]
+        y: i32,
+        #[builder(setter(skip), default = 3)]
+        z: i32,
+    }
+
+    impl Foo {
+        fn __new(x: i32, y: i32, z: i32) -> Self {
+            Self { x: x * 10, y, z }
+        }
+    }
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(build_via = Bar::try_new, build_method(into = Result<Bar, String>))]
+    struct Bar {
+        x: i32,
+    }
+
+    impl Bar {
+        fn try_new(x: i32) -> Result<Self, String> {
+            if x < 0 {
+                Err("negative".to_owned())
+            } else {
+                Ok(Self { x })
+            }
+        }
+    }
+
+    assert_eq!(Foo::builder().x(1).build(), Foo { x: 10, y: 2, z: 3 });
+    assert_eq!(Bar::builder().x(1).build(), Ok(Bar { x: 1 }));
+    assert_eq!(Bar::builder().x(-1).build(), Err("negative".to_owned()));
+}
+
+#[test]
+fn test_strip_option_custom_type() {
+    #[derive(PartialEq, Debug)]
+    enum Maybe<T> {
+        Just(T),
+        Nothing,
+    }
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(strip_option(ty = Maybe, some = Maybe::Just, none = Maybe::Nothing)))]
+        x: Maybe<i32>,
+        #[builder(setter(into, strip_option(ty = Maybe, some = Maybe::Just, none = Maybe::Nothing)))]
+        y: Maybe<String>,
+        #[builder(default = Maybe::Just(2), setter(strip_option(ty = Maybe, some = Maybe::Just, none = Maybe::Nothing)))]
+        z: Maybe<i32>,
+    }
+
+    assert_eq!(
+        Foo::builder().build(),
+        Foo {
+            x: Maybe::Nothing,
+            y: Maybe::Nothing,
+            z: Maybe::Just(2)
+        }
+    );
+    assert_eq!(
+        Foo::builder().x(1).y("a").z(3).build(),
+        Foo {
+            x: Maybe::Just(1),
+            y: Maybe::Just("a".to_owned()),
+            z: Maybe::Just(3)
+        }
+    );
+}
+
+#[test]
+fn test_from_fn() {
+    use std::collections::HashMap;
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(from_fn))]
+        x: Vec<i32>,
+        #[builder(default, setter(from_fn))]
+        y: HashMap<String, i32>,
+    }
+
+    let mut counter = 0;
+    let foo = Foo::builder()
+        .x(|| {
+            counter += 1;
+            (counter <= 3).then_some(counter)
+        })
+        .build();
+    assert_eq!(
+        foo,
+        Foo {
+            x: vec![1, 2, 3],
+            y: HashMap::new()
+        }
+    );
+
+    let mut entries = vec![("a".to_owned(), 1), ("b".to_owned(), 2)].into_iter();
+    let foo = Foo::builder().x(|| None).y(|| entries.next()).build();
+    assert_eq!(foo.x, Vec::<i32>::new());
+    assert_eq!(foo.y, HashMap::from([("a".to_owned(), 1), ("b".to_owned(), 2)]));
+}
+
+#[test]
+fn test_mutator_requirements() {
+    #[derive(TypedBuilder)]
+    #[builder(mutators(
+        fn inc(&mut self) {
+            self.count += 1;
+        }
+        #[mutator(requires = [y, x])]
+        fn sum(&mut self) {
+            self.count += self.x + self.y;
+        }
+    ))]
+    #[allow(dead_code)]
+    struct Foo {
+        x: i32,
+        y: i32,
+        #[builder(via_mutators)]
+        count: i32,
+    }
+
+    assert_eq!(
+        FooBuilder::MUTATOR_REQUIREMENTS,
+        &[("inc", &[] as &[&str]), ("sum", &["x", "y"])]
+    );
+}
+
+#[test]
+fn test_short_circuit() {
+    #[derive(PartialEq, Debug, Clone, TypedBuilder)]
+    #[builder(short_circuit(field = use_cached, returns = Foo { x: 0, y: "cached".to_owned(), use_cached: true }))]
+    struct Foo {
+        x: i32,
+        #[builder(default, setter(into))]
+        y: String,
+        #[builder(setter(strip_bool))]
+        use_cached: bool,
+    }
+
+    assert_eq!(
+        Foo::builder().x(1).y("a").build(),
+        Foo {
+            x: 1,
+            y: "a".to_owned(),
+            use_cached: false
+        }
+    );
+    assert_eq!(
+        Foo::builder().x(1).y("a").use_cached().build(),
+        Foo {
+            x: 0,
+            y: "cached".to_owned(),
+            use_cached: true
+        }
+    );
+}
+
+#[test]
+fn test_reset() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(reset)]
+    struct Foo {
+        x: i32,
+        #[builder(default = 2)]
+        y: i32,
+        #[builder(setter(skip), default = x + y)]
+        z: i32,
+    }
+
+    let foo = Foo::builder().x(1).y(3).build();
+    assert_eq!(foo, Foo { x: 1, y: 3, z: 4 });
+    assert_eq!(foo.reset().x(5).build(), Foo { x: 5, y: 2, z: 7 });
+    assert_eq!(Foo::into_fresh_builder().x(1).build(), Foo { x: 1, y: 2, z: 3 });
+}
+
+#[test]
+fn test_emit_schema() {
+    #[derive(TypedBuilder)]
+    #[builder(emit_schema)]
+    #[allow(dead_code)]
+    struct Foo {
+        x: i32,
+        #[builder(setter(into))]
+        y: String,
+        #[builder(default, setter(strip_option))]
+        z: Option<Vec<u8>>,
+        #[builder(default, setter(try_into))]
+        w: u8,
+    }
+
+    assert_eq!(
+        FooBuilder::SCHEMA,
+        "x(x: i32)\n\
+         y(y: impl Into<String>)\n\
+         z(z: Vec<u8>) (optional)\n\
+         w<__TryIntoValue: TryInto<u8>>(w: __TryIntoValue) -> Result (optional)\n"
+    );
+}
+
+#[test]
+fn test_each_max() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo {
+        #[builder(default, setter(each(name = "item", max = 2)))]
+        items: Vec<i32>,
+    }
+
+    assert_eq!(Foo::builder().item(1).item(2).build(), Foo { items: vec![1, 2] });
+    assert_eq!(Foo::builder().items(vec![1, 2]).build(), Foo { items: vec![1, 2] });
+
+    let result = std::panic::catch_unwind(|| Foo::builder().item(1).item(2).item(3));
+    assert!(result.is_err());
+
+    let result = std::panic::catch_unwind(|| Foo::builder().items(vec![1, 2, 3]).build());
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_each_max_boundaries() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo {
+        #[builder(default, setter(each(name = "one", max = 1)))]
+        ones: Vec<i32>,
+        #[builder(default = vec![1, 2], setter(each(name = "item", max = 2)))]
+        items: Vec<i32>,
+    }
+
+    assert_eq!(Foo::builder().one(1).build().ones, vec![1]);
+    let result = std::panic::catch_unwind(|| Foo::builder().one(1).one(2));
+    assert!(result.is_err());
+
+    // The items of the default count towards the maximum.
+    let result = std::panic::catch_unwind(|| Foo::builder().item(3));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_take() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(take))]
+        x: Vec<i32>,
+        #[builder(default, setter(take, strip_option))]
+        y: Option<String>,
+    }
+
+    let mut x = (0..1000).collect::<Vec<_>>();
+    let mut y = "y".to_owned();
+    let foo = Foo::builder().x(&mut x).y(&mut y).build();
+    assert!(x.is_empty());
+    assert!(y.is_empty());
+    assert_eq!(foo.x.len(), 1000);
+    assert_eq!(foo.y.as_deref(), Some("y"));
+}
+
+#[test]
+fn test_recipe_build_iter() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(recipe)]
+    struct Foo {
+        name: String,
+        #[builder(default = COUNTER.fetch_add(1, Ordering::Relaxed))]
+        id: usize,
+    }
+
+    let instances = Foo::builder()
+        .name("foo".to_owned())
+        .into_recipe()
+        .build_iter(3)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        instances,
+        vec![
+            Foo {
+                name: "foo".to_owned(),
+                id: 0
+            },
+            Foo {
+                name: "foo".to_owned(),
+                id: 1
+            },
+            Foo {
+                name: "foo".to_owned(),
+                id: 2
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_lazy_skip() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::OnceLock;
+
+    // A minimal `LazyLock`, which is newer than the MSRV.
+    struct Lazy<T> {
+        cell: OnceLock<T>,
+        init: fn() -> T,
+    }
+
+    impl<T> Lazy<T> {
+        fn new(init: fn() -> T) -> Self {
+            Self {
+                cell: OnceLock::new(),
+                init,
+            }
+        }
+    }
+
+    impl<T> core::ops::Deref for Lazy<T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            self.cell.get_or_init(self.init)
+        }
+    }
+
+    static COMPUTED: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(TypedBuilder)]
+    struct Foo {
+        x: i32,
+        #[builder(setter(skip, lazy), default = {
+            COMPUTED.fetch_add(1, Ordering::Relaxed);
+            vec![1, 2, 3]
+        })]
+        expensive: Lazy<Vec<i32>>,
+    }
+
+    let foo = Foo::builder().x(1).build();
+    assert_eq!(foo.x, 1);
+    assert_eq!(COMPUTED.load(Ordering::Relaxed), 0);
+    assert_eq!(*foo.expensive, vec![1, 2, 3]);
+    assert_eq!(foo.expensive.len(), 3);
+    assert_eq!(COMPUTED.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn test_cfg_gated_builder() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(cfg = "all()")]
+    struct Enabled {
+        x: i32,
+    }
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(cfg = "any()")]
+    struct Disabled {
+        x: i32,
+    }
+
+    // Would conflict with the generated method if it wasn't gated away.
+    impl Disabled {
+        fn builder() -> Self {
+            Self { x: 2 }
+        }
+    }
+
+    assert!(Enabled::builder().x(1).build() == Enabled { x: 1 });
+    assert!(Disabled::builder() == Disabled { x: 2 });
+}
+
+#[test]
+fn test_unit_setters() {
+    use std::time::Duration;
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(unit = "secs", unit = "millis"))]
+        timeout: Duration,
+        #[builder(default, setter(strip_option, unit = "secs_f64"))]
+        delay: Option<Duration>,
+    }
+
+    assert!(
+        Foo::builder().timeout_secs(3).build()
+            == Foo {
+                timeout: Duration::from_secs(3),
+                delay: None,
+            }
+    );
+    assert!(
+        Foo::builder().timeout_millis(250).delay_secs_f64(0.5).build()
+            == Foo {
+                timeout: Duration::from_millis(250),
+                delay: Some(Duration::from_millis(500)),
+            }
+    );
+    assert!(
+        Foo::builder().timeout(Duration::from_secs(1)).build()
+            == Foo {
+                timeout: Duration::from_secs(1),
+                delay: None,
+            }
+    );
+}
+
+#[test]
+fn test_build_method_post() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(
+        mutators(
+            #[mutator(auto)]
+            fn sort_bounds(&mut self) {
+                if self.inclusive && self.min > self.max {
+                    core::mem::swap(&mut self.min, &mut self.max);
+                }
+            }
+        ),
+        build_method(post = |range: Range| -> Result<Range, String> {
+            if range.min < range.max || (range.inclusive && range.min == range.max) {
+                Ok(range)
+            } else {
+                Err(format!("empty range {}..{}", range.min, range.max))
+            }
+        })
+    )]
+    struct Range {
+        min: i32,
+        max: i32,
+        #[builder(default)]
+        inclusive: bool,
+    }
+
+    assert_eq!(
+        Range::builder().min(3).max(1).inclusive(true).build(),
+        Ok(Range {
+            min: 1,
+            max: 3,
+            inclusive: true
+        })
+    );
+    assert_eq!(Range::builder().min(3).max(1).build(), Err("empty range 3..1".to_owned()));
+}
+
+#[test]
+fn test_enum_variant_builders() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(build_method(into))]
+    #[allow(dead_code)]
+    enum Event<T> {
+        Created {
+            id: u32,
+            #[builder(default)]
+            payload: Option<T>,
+        },
+        #[builder(builder_type(name = DeletionBuilder))]
+        Deleted {
+            id: u32,
+            #[builder(default = true)]
+            soft: bool,
+        },
+        Cleared,
+        Renamed(String),
+    }
+
+    let created: Event<&str> = Event::created_builder().id(1).build();
+    assert_eq!(created, Event::Created { id: 1, payload: None });
+    let created: Event<&str> = Event::created_builder().payload(Some("data")).id(2).build();
+    assert_eq!(
+        created,
+        Event::Created {
+            id: 2,
+            payload: Some("data")
+        }
+    );
+    let deletion: DeletionBuilder<(), _> = Event::deleted_builder();
+    let deleted: Event<()> = deletion.id(3).build();
+    assert_eq!(deleted, Event::Deleted { id: 3, soft: true });
+}
+
+#[test]
+fn test_tuple_struct() {
+    #[derive(PartialEq, Debug, Default, TypedBuilder)]
+    #[builder(apply_method)]
+    struct Rgb(u8, #[builder(setter(name = green))] u8, #[builder(default = _0)] u8);
+
+    assert!(Rgb::builder()._0(1).green(2).build() == Rgb(1, 2, 1));
+    assert!(Rgb::builder().green(2)._0(1)._2(3).build() == Rgb(1, 2, 3));
+
+    let mut rgb = Rgb::default();
+    Rgb::builder()._2(5).apply_to(&mut rgb);
+    assert!(rgb == Rgb(0, 0, 5));
+}
+
+#[test]
+fn test_function_builder() {
+    #[typed_builder::builder]
+    fn describe<'a, T: core::fmt::Debug>(
+        label: &'a str,
+        value: T,
+        #[builder(default = Some(1), setter(strip_option))] repeat: Option<usize>,
+    ) -> String {
+        format!("{label}: {value:?}").repeat(repeat.unwrap_or(0))
+    }
+
+    #[typed_builder::builder(build_method(name = run))]
+    fn count(#[builder(default)] mut from: u32, to: u32) -> Vec<u32> {
+        let mut result = Vec::new();
+        while from < to {
+            result.push(from);
+            from += 1;
+        }
+        result
+    }
+
+    assert_eq!(describe().label("x").value(1).call(), "x: 1");
+    assert_eq!(describe().value("y").repeat(2).label("x").call(), "x: \"y\"x: \"y\"");
+    assert_eq!(count().to(3).run(), vec![0, 1, 2]);
+    assert_eq!(count().from(2).to(4).run(), vec![2, 3]);
+}
+
+#[test]
+fn test_constructor_builder() {
+    #[derive(PartialEq, Debug)]
+    struct Span<T> {
+        start: T,
+        end: T,
+    }
+
+    #[typed_builder::builder(field_defaults(default))]
+    impl<T: PartialOrd + Default> Span<T> {
+        #[builder]
+        fn new(start: T, end: T) -> Self {
+            assert!(start <= end, "the start of a span cannot be after its end");
+            Self { start, end }
+        }
+
+        #[builder(build_method(name = try_build))]
+        fn up_to(end: T) -> Result<Self, &'static str> {
+            if end < T::default() {
+                return Err("negative end");
+            }
+            Ok(Self::new(T::default(), end))
+        }
+    }
+
+    assert_eq!(Span::builder().start(1).end(2).build(), Span { start: 1, end: 2 });
+    assert_eq!(Span::builder().end(2).build(), Span { start: 0, end: 2 });
+    assert_eq!(Span::up_to_builder().end(3).try_build(), Ok(Span { start: 0, end: 3 }));
+    assert_eq!(Span::up_to_builder().end(-3).try_build(), Err("negative end"));
+
+    let result = std::panic::catch_unwind(|| Span::builder().start(2).end(1).build());
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_remote() {
+    mod foreign {
+        #[derive(PartialEq, Debug)]
+        pub struct Pair<T> {
+            pub left: T,
+            pub right: T,
+        }
+
+        #[derive(PartialEq, Debug)]
+        pub struct Opaque(u32);
+
+        impl Opaque {
+            pub fn new(value: u32) -> Self {
+                Self(value)
+            }
+        }
+    }
+
+    #[derive(TypedBuilder)]
+    #[builder(remote = "foreign::Pair<T>")]
+    struct PairDef<T: Default> {
+        left: T,
+        #[builder(default)]
+        right: T,
+    }
+
+    #[derive(TypedBuilder)]
+    #[builder(remote = "foreign::Opaque", build_via = foreign::Opaque::new)]
+    struct OpaqueDef {
+        value: u32,
+    }
+
+    assert_eq!(PairDef::builder().left(1).build(), foreign::Pair { left: 1, right: 0 });
+    assert_eq!(OpaqueDef::builder().value(2).build(), foreign::Opaque::new(2));
+}
+
+#[test]
+fn test_const_generic_params() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(mutators(
+        fn bump(&mut self) {
+            self.data[0] += 1;
+        }
+        #[mutator(auto)]
+        fn mark_last(&mut self) {
+            self.data[N - 1] = 9;
+        }
+    ))]
+    struct Buf<const N: usize> {
+        #[builder(via_mutators(init = [0; N]))]
+        data: [u8; N],
+        #[builder(setter(into))]
+        label: String,
+    }
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Mixed<'a, T: Default + Copy, const N: usize> {
+        name: &'a str,
+        #[builder(default = [T::default(); N])]
+        values: [T; N],
+    }
+
+    assert_eq!(
+        Buf::<3>::builder().label("foo").bump().bump().build(),
+        Buf {
+            data: [2, 0, 9],
+            label: "foo".to_owned(),
+        }
+    );
+
+    assert_eq!(
+        Mixed::<i32, 2>::builder().name("bar").build(),
+        Mixed {
+            name: "bar",
+            values: [0, 0]
+        }
+    );
+    assert_eq!(
+        Mixed::builder().values([1, 2, 3]).name("baz").build(),
+        Mixed {
+            name: "baz",
+            values: [1, 2, 3]
+        }
+    );
+}
+
+#[test]
+fn test_default_generic_params() {
+    #[derive(PartialEq, Debug, Clone, TypedBuilder)]
+    #[builder(recipe, mutators(
+        fn bump(&mut self) {
+            self.count += 1;
+        }
+    ))]
+    struct Foo<T: Default + Clone = String, const N: usize = 2> {
+        #[builder(default)]
+        x: T,
+        #[builder(default = [0; N])]
+        array: [u8; N],
+        #[builder(via_mutators)]
+        count: u8,
+    }
+
+    let builder: FooBuilder = <Foo>::builder();
+    let recipe: FooRecipe = builder.bump().into_recipe();
+    assert_eq!(
+        recipe.build(),
+        Foo {
+            x: String::new(),
+            array: [0, 0],
+            count: 1,
+        }
+    );
+
+    let foo: Foo<u32, 3> = Foo::builder().x(1).build();
+    assert_eq!(
+        foo,
+        Foo {
+            x: 1,
+            array: [0, 0, 0],
+            count: 0,
+        }
+    );
+}
+
+#[test]
+fn test_where_clause_with_associated_types() {
+    #[derive(Clone, TypedBuilder)]
+    #[builder(recipe, mutators(
+        fn push_default(&mut self) {
+            self.items.push(Default::default());
+        }
+    ))]
+    struct Foo<T>
+    where
+        T: Iterator + Clone,
+        T::Item: Clone + Default,
+    {
+        source: T,
+        #[builder(via_mutators)]
+        items: Vec<T::Item>,
+        #[builder(default, setter(strip_option))]
+        first: Option<T::Item>,
+    }
+
+    let foo = Foo::builder().push_default().source(1..3).first(3).build();
+    assert_eq!((foo.source, foo.items, foo.first), (1..3, vec![0], Some(3)));
+
+    let foo = Foo::builder().source(1..3).into_recipe().build();
+    assert_eq!((foo.source, foo.items, foo.first), (1..3, vec![], None));
+}
+
+#[test]
+fn test_cfg_on_fields_and_parameters() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo {
+        x: i32,
+        #[cfg(not(test))]
+        y: i32,
+        #[cfg(test)]
+        #[builder(default = 2)]
+        z: i32,
+    }
+
+    assert_eq!(Foo::builder().x(1).build(), Foo { x: 1, z: 2 });
+
+    #[typed_builder::builder]
+    fn sum(x: i32, #[cfg(not(test))] y: i32, #[cfg(test)] z: i32) -> i32 {
+        #[cfg(not(test))]
+        return x + y + z;
+        #[cfg(test)]
+        return x + z;
+    }
+
+    assert_eq!(sum().x(1).z(2).call(), 3);
+
+    struct Bar(i32);
+
+    #[typed_builder::builder]
+    impl Bar {
+        #[builder]
+        fn new(#[cfg(test)] x: i32, #[cfg(not(test))] y: i32) -> Self {
+            #[cfg(test)]
+            return Self(x);
+            #[cfg(not(test))]
+            return Self(y);
+        }
+    }
+
+    assert_eq!(Bar::builder().x(1).build().0, 1);
+}
+
+#[test]
+fn test_into_set_generic_impl_into() {
+    #[derive(TypedBuilder)]
+    #[builder(build_method(into))]
+    struct Foo {
+        value: i32,
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Bar {
+        value: i32,
+    }
+
+    impl From<Foo> for Bar {
+        fn from(val: Foo) -> Self {
+            Self { value: val.value }
+        }
+    }
+
+    let bar: Bar = Foo::builder().value(42).build();
+    assert_eq!(bar, Bar { value: 42 });
+}
+
+#[test]
+fn test_prefix() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(field_defaults(setter(prefix = "with_")))]
+    struct Foo {
+        x: i32,
+        y: i32,
+    }
+
+    let foo = Foo::builder().with_x(1).with_y(2).build();
+    assert_eq!(foo, Foo { x: 1, y: 2 });
+}
+
+#[test]
+fn test_suffix() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(field_defaults(setter(suffix = "_value")))]
+    struct Foo {
+        x: i32,
+        y: i32,
+    }
+
+    let foo = Foo::builder().x_value(1).y_value(2).build();
+    assert_eq!(foo, Foo { x: 1, y: 2 });
+}
+
+#[test]
+fn test_prefix_and_suffix() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(field_defaults(setter(prefix = "with_", suffix = "_value")))]
+    struct Foo {
+        x: i32,
+        y: i32,
+    }
+
+    let foo = Foo::builder().with_x_value(1).with_y_value(2).build();
+    assert_eq!(foo, Foo { x: 1, y: 2 });
+}
+
+#[test]
+fn test_issue_118() {
+    #[derive(TypedBuilder)]
+    #[builder(build_method(into=Bar))]
+    struct Foo<T> {
+        #[builder(default, setter(skip))]
+        #[allow(dead_code)]
+        foo: Option<T>,
+    }
+
+    struct Bar;
+
+    impl<T> From<Foo<T>> for Bar {
+        fn from(_value: Foo<T>) -> Self {
+            Self
+        }
+    }
+
+    let _ = Foo::<u32>::builder().build();
+}
+
+#[test]
+fn test_mutable_defaults() {
+    #[derive(TypedBuilder, PartialEq, Debug)]
+    struct Foo {
+        #[builder(default, mutable_during_default_resolution, setter(strip_option))]
+        x: Option<i32>,
+        #[builder(default = if let Some(x) = x.as_mut() {
+            *x *= 2;
+            *x
+        } else {
+            Default::default()
+        })]
+        y: i32,
+    }
+
+    let foo = Foo::builder().x(5).build();
+
+    assert_eq!(foo, Foo { x: Some(10), y: 10 });
+}
+
+#[test]
+fn test_preinitialized_fields() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Foo {
+        x: i32,
+        #[builder(via_mutators)]
+        y: i32,
+        #[builder(via_mutators = 2)]
+        z: i32,
+        #[builder(via_mutators(init = 2))]
+        w: i32,
+    }
+
+    let foo = Foo::builder().x(1).build();
+    assert_eq!(foo, Foo { x: 1, y: 0, z: 2, w: 2 });
+}
+
+#[test]
+fn test_mutators_item() {
     #[derive(Debug, PartialEq, TypedBuilder)]
     #[builder(mutators(
         #[mutator(requires = [x])]
@@ -936,3 +3401,89 @@ fn test_mutators_field() {
     let foo = Foo::builder().x(1).y(1).inc_y_by_x().build();
     assert_eq!(foo, Foo { x: 1, y: 2, z: 2, w: 2 });
 }
+
+#[test]
+fn test_union() {
+    #[derive(Clone, Copy, TypedBuilder)]
+    union Foo<T: Copy> {
+        x: T,
+        #[builder(setter(into))]
+        y: f64,
+    }
+
+    let foo = Foo::<u32>::builder().x(1).build();
+    assert_eq!(unsafe { foo.x }, 1);
+    let foo = Foo::<u32>::builder().y(1.5f32).build();
+    assert!((unsafe { foo.y } - 1.5).abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_mutators_run_before_defaults() {
+    use std::cell::RefCell;
+
+    thread_local! {
+        static LOG: RefCell<Vec<&'static str>> = RefCell::default();
+    }
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(mutators(
+        fn log_mutator(&mut self) {
+            LOG.with(|log| log.borrow_mut().push("mutator"));
+            self.x += 1;
+        }
+    ))]
+    struct Foo {
+        #[builder(via_mutators)]
+        x: i32,
+        #[builder(default = LOG.with(|log| { log.borrow_mut().push("default"); x * 10 }))]
+        y: i32,
+    }
+
+    let foo = Foo::builder().log_mutator().build();
+    assert_eq!(foo, Foo { x: 1, y: 10 });
+    assert_eq!(LOG.with(|log| log.borrow().clone()), ["mutator", "default"]);
+}
+
+#[test]
+fn test_mutators_run() {
+    use std::cell::RefCell;
+
+    thread_local! {
+        static LOG: RefCell<Vec<&'static str>> = RefCell::default();
+    }
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(mutators(
+        #[mutator(auto)]
+        fn double(&mut self) {
+            LOG.with(|log| log.borrow_mut().push("mutator"));
+            self.x *= 2;
+        }
+    ))]
+    struct AfterDefaults {
+        x: i32,
+        #[builder(default = LOG.with(|log| { log.borrow_mut().push("default"); x + 1 }))]
+        y: i32,
+    }
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(mutators_run = before_defaults, mutators(
+        #[mutator(auto)]
+        fn double(&mut self) {
+            LOG.with(|log| log.borrow_mut().push("mutator"));
+            self.x *= 2;
+        }
+    ))]
+    struct BeforeDefaults {
+        x: i32,
+        #[builder(default = LOG.with(|log| { log.borrow_mut().push("default"); x + 1 }))]
+        y: i32,
+    }
+
+    assert_eq!(AfterDefaults::builder().x(1).build(), AfterDefaults { x: 2, y: 2 });
+    assert_eq!(LOG.with(RefCell::take), ["default", "mutator"]);
+
+    // The default sees the mutated value.
+    assert_eq!(BeforeDefaults::builder().x(1).build(), BeforeDefaults { x: 2, y: 3 });
+    assert_eq!(LOG.with(RefCell::take), ["mutator", "default"]);
+}
@@ -1,10 +1,11 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
 use syn::parse::Error;
+use syn::spanned::Spanned;
 
-use crate::field_info::FieldBuilderAttr;
+use crate::field_info::{AutoInto, FieldBuilderAttr};
 use crate::mutator::Mutator;
-use crate::util::{path_to_single_string, ApplyMeta, AttrArg};
+use crate::util::{expr_to_field_names, path_to_single_string, ApplyMeta, AttrArg};
 
 #[derive(Debug, Default, Clone)]
 pub struct CommonDeclarationSettings {
@@ -29,10 +30,7 @@ impl ApplyMeta for CommonDeclarationSettings {
                 self.doc = Some(expr.key_value()?.parse_value()?);
                 Ok(())
             }
-            _ => Err(Error::new_spanned(
-                expr.name(),
-                format!("Unknown parameter {:?}", expr.name().to_string()),
-            )),
+            _ => Err(expr.unknown_parameter(&["vis", "name", "doc"])),
         }
     }
 }
@@ -53,9 +51,10 @@ impl CommonDeclarationSettings {
 }
 
 /// Setting of the `into` argument.
-#[derive(Debug, Clone)]
+#[derive(Debug, Default, Clone)]
 pub enum IntoSetting {
     /// Do not run any conversion on the built value.
+    #[default]
     NoConversion,
     /// Convert the build value into the generic parameter passed to the `build` method.
     GenericConversion,
@@ -63,23 +62,75 @@ pub enum IntoSetting {
     TypeConversionToSpecificType(syn::TypePath),
 }
 
-impl Default for IntoSetting {
-    fn default() -> Self {
-        Self::NoConversion
-    }
-}
-
 #[derive(Debug, Default, Clone)]
 pub struct BuildMethodSettings {
     pub common: CommonDeclarationSettings,
 
     /// Whether to convert the built type into another while finishing the build.
     pub into: IntoSetting,
+
+    /// Return the built type as an opaque `impl Trait` instead.
+    pub returns_impl: Option<syn::TypeImplTrait>,
+
+    /// Also generate a `<build>_or_default` method that uses `Default::default()` for unset required fields.
+    pub or_default: Option<Span>,
+
+    /// Return the built value pinned in a `Box`.
+    pub pinned: Option<Span>,
+
+    /// A fallible closure the built value is passed through last, making the build method return a
+    /// `Result`.
+    pub post: Option<PostBuild>,
+
+    /// The error type of the build method, that the errors of deferred `try_into` setters are
+    /// converted into.
+    pub error: Option<syn::Type>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PostBuild {
+    pub closure: syn::ExprClosure,
+    /// The `E` of the closure's `Result<_, E>` return type.
+    pub error_type: syn::Type,
+}
+
+impl PostBuild {
+    fn new(closure: syn::ExprClosure) -> Result<Self, Error> {
+        let error_type = match &closure.output {
+            syn::ReturnType::Type(_, ty) => match &**ty {
+                syn::Type::Path(type_path) if type_path.qself.is_none() => {
+                    let segment = type_path.path.segments.last().expect("paths are not empty");
+                    match &segment.arguments {
+                        syn::PathArguments::AngleBracketed(args) if segment.ident == "Result" && args.args.len() == 2 => {
+                            match &args.args[1] {
+                                syn::GenericArgument::Type(error_type) => Some(error_type.clone()),
+                                _ => None,
+                            }
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            },
+            syn::ReturnType::Default => None,
+        };
+        let error_type = error_type.ok_or_else(|| {
+            Error::new_spanned(
+                &closure,
+                "`post` must be a closure with an explicit `-> Result<..., E>` return type",
+            )
+        })?;
+        Ok(Self { closure, error_type })
+    }
 }
 
 impl ApplyMeta for BuildMethodSettings {
     fn apply_meta(&mut self, expr: AttrArg) -> Result<(), Error> {
         match expr.name().to_string().as_str() {
+            "into" if self.returns_impl.is_some() => Err(Error::new_spanned(
+                expr.name(),
+                "`into` cannot be used together with `returns_impl`",
+            )),
             "into" => match expr {
                 AttrArg::Flag(_) => {
                     self.into = IntoSetting::GenericConversion;
@@ -92,7 +143,230 @@ impl ApplyMeta for BuildMethodSettings {
                 }
                 _ => Err(expr.incorrect_type()),
             },
-            _ => self.common.apply_meta(expr),
+            "returns_impl" => {
+                if !matches!(self.into, IntoSetting::NoConversion) {
+                    return Err(Error::new_spanned(
+                        expr.name(),
+                        "`returns_impl` cannot be used together with `into`",
+                    ));
+                }
+                let bounds = expr.key_value()?.value;
+                self.returns_impl = Some(syn::parse2(quote!(impl #bounds))?);
+                Ok(())
+            }
+            "or_default" => expr.apply_flag_to_field(&mut self.or_default, "generating a build_or_default method"),
+            "pinned" => expr.apply_flag_to_field(&mut self.pinned, "returning a pinned box"),
+            "post" => {
+                self.post = Some(PostBuild::new(expr.key_value()?.parse_value()?)?);
+                Ok(())
+            }
+            "error" => {
+                self.error = Some(expr.key_value()?.parse_value()?);
+                Ok(())
+            }
+            "vis" | "name" | "doc" => self.common.apply_meta(expr),
+            _ => Err(expr.unknown_parameter(&[
+                "into",
+                "returns_impl",
+                "or_default",
+                "pinned",
+                "post",
+                "error",
+                "vis",
+                "name",
+                "doc",
+            ])),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct ApplyMethodSettings {
+    pub common: CommonDeclarationSettings,
+
+    /// Also assign the defaults of the defaulted fields that were not set.
+    pub defaults: Option<Span>,
+}
+
+impl ApplyMeta for ApplyMethodSettings {
+    fn apply_meta(&mut self, expr: AttrArg) -> Result<(), Error> {
+        match expr.name().to_string().as_str() {
+            "defaults" => expr.apply_flag_to_field(&mut self.defaults, "assigning the defaults"),
+            "vis" | "name" | "doc" => self.common.apply_meta(expr),
+            _ => Err(expr.unknown_parameter(&["defaults", "vis", "name", "doc"])),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct RecipeSettings {
+    pub common: CommonDeclarationSettings,
+
+    /// Resolve the defaults of the unset fields once, in `into_recipe()`, instead of on every build.
+    pub capture_defaults: Option<Span>,
+}
+
+impl ApplyMeta for RecipeSettings {
+    fn apply_meta(&mut self, expr: AttrArg) -> Result<(), Error> {
+        match expr.name().to_string().as_str() {
+            "capture_defaults" => expr.apply_flag_to_field(&mut self.capture_defaults, "capturing the defaults"),
+            "vis" | "name" | "doc" => self.common.apply_meta(expr),
+            _ => Err(expr.unknown_parameter(&["capture_defaults", "vis", "name", "doc"])),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct BuilderMethodSettings {
+    pub common: CommonDeclarationSettings,
+
+    /// Parameters of the builder method, each pre-setting the field with the same name.
+    pub params: Vec<syn::PatType>,
+}
+
+impl ApplyMeta for BuilderMethodSettings {
+    fn apply_meta(&mut self, expr: AttrArg) -> Result<(), Error> {
+        match expr.name().to_string().as_str() {
+            "params" => {
+                for arg in expr.sub_attr()?.args::<syn::FnArg>()? {
+                    match arg {
+                        syn::FnArg::Typed(pat_type) => self.params.push(pat_type),
+                        syn::FnArg::Receiver(receiver) => {
+                            return Err(Error::new_spanned(receiver, "the builder method cannot take `self`"))
+                        }
+                    }
+                }
+                Ok(())
+            }
+            "vis" | "name" | "doc" => self.common.apply_meta(expr),
+            _ => Err(expr.unknown_parameter(&["params", "vis", "name", "doc"])),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct BuilderTypeSettings {
+    pub common: CommonDeclarationSettings,
+
+    /// Traits the builder type implements with an empty `impl` block, for all type-states.
+    pub impl_traits: Vec<syn::Path>,
+
+    /// Generate a `Debug` impl for the builder type that shows the fields that were set.
+    pub derive_debug: Option<Span>,
+
+    /// Fields the `Debug` impl of the builder type never shows.
+    pub debug_skip: Vec<syn::Ident>,
+
+    /// Generate a `PartialEq` impl for the builder type.
+    pub derive_partial_eq: Option<Span>,
+
+    /// Generate `Eq` and `Hash` impls for the builder type.
+    pub derive_eq: Option<Span>,
+    pub derive_hash: Option<Span>,
+
+    /// The `cfg` predicates the derived impls are conditional on, if they were requested with
+    /// `cfg_derive(...)`.
+    pub debug_cfg: Option<syn::Meta>,
+    pub partial_eq_cfg: Option<syn::Meta>,
+    pub eq_cfg: Option<syn::Meta>,
+    pub hash_cfg: Option<syn::Meta>,
+}
+
+impl BuilderTypeSettings {
+    fn apply_derive(&mut self, path: syn::Path, cfg: Option<syn::Meta>) -> Result<(), Error> {
+        match path_to_single_string(&path).as_deref() {
+            // The builder type always implements `Clone`.
+            Some("Clone") => {}
+            Some("Debug") => {
+                self.derive_debug = Some(path.span());
+                self.debug_cfg = cfg;
+            }
+            Some("PartialEq") => {
+                self.derive_partial_eq = Some(path.span());
+                self.partial_eq_cfg = cfg;
+            }
+            Some("Eq") => {
+                self.derive_eq = Some(path.span());
+                self.eq_cfg = cfg;
+            }
+            Some("Hash") => {
+                self.derive_hash = Some(path.span());
+                self.hash_cfg = cfg;
+            }
+            _ => return Err(Error::new_spanned(
+                path,
+                "Unsupported derive for the builder type - supported derives are `Clone`, `Debug`, `PartialEq`, `Eq` and `Hash`",
+            )),
+        }
+        Ok(())
+    }
+}
+
+impl ApplyMeta for BuilderTypeSettings {
+    fn apply_meta(&mut self, expr: AttrArg) -> Result<(), Error> {
+        match expr.name().to_string().as_str() {
+            "impl_trait" => {
+                self.impl_traits.push(expr.key_value()?.parse_value()?);
+                Ok(())
+            }
+            "derive" => {
+                for path in expr.sub_attr()?.args::<syn::Path>()? {
+                    self.apply_derive(path, None)?;
+                }
+                Ok(())
+            }
+            "cfg_derive" => {
+                let sub = expr.sub_attr()?;
+                let name = sub.name.clone();
+                let mut args = sub.args::<syn::Meta>()?.into_iter();
+                let Some(cfg) = args.next() else {
+                    return Err(Error::new_spanned(
+                        name,
+                        "`cfg_derive(...)` requires a `cfg` predicate followed by the traits to derive",
+                    ));
+                };
+                for meta in args {
+                    let syn::Meta::Path(path) = meta else {
+                        return Err(Error::new_spanned(meta, "Expected a trait to derive"));
+                    };
+                    self.apply_derive(path, Some(cfg.clone()))?;
+                }
+                Ok(())
+            }
+            "debug" => {
+                let sub = expr.sub_attr()?;
+                self.derive_debug.get_or_insert(sub.name.span());
+                for arg in sub.args::<AttrArg>()? {
+                    if arg.name() != "skip" {
+                        return Err(arg.unknown_parameter(&["skip"]));
+                    }
+                    self.debug_skip.extend(expr_to_field_names(arg.key_value()?.parse_value()?)?);
+                }
+                Ok(())
+            }
+            "vis" | "name" | "doc" => self.common.apply_meta(expr),
+            _ => Err(expr.unknown_parameter(&["impl_trait", "derive", "cfg_derive", "debug", "vis", "name", "doc"])),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct PartialTypeSettings {
+    pub common: CommonDeclarationSettings,
+
+    /// Derives to put on the partial type, e.g. for deserializing it.
+    pub derives: Vec<syn::Path>,
+}
+
+impl ApplyMeta for PartialTypeSettings {
+    fn apply_meta(&mut self, expr: AttrArg) -> Result<(), Error> {
+        match expr.name().to_string().as_str() {
+            "derive" => {
+                self.derives.extend(expr.sub_attr()?.args::<syn::Path>()?);
+                Ok(())
+            }
+            "vis" | "name" | "doc" => self.common.apply_meta(expr),
+            _ => Err(expr.unknown_parameter(&["derive", "vis", "name", "doc"])),
         }
     }
 }
@@ -103,20 +377,72 @@ pub struct TypeBuilderAttr<'a> {
     pub doc: bool,
 
     /// Customize builder method, ex. visibility, name
-    pub builder_method: CommonDeclarationSettings,
+    pub builder_method: BuilderMethodSettings,
 
     /// Customize builder type, ex. visibility, name
-    pub builder_type: CommonDeclarationSettings,
+    pub builder_type: BuilderTypeSettings,
 
     /// Customize build method, ex. visibility, name
     pub build_method: BuildMethodSettings,
 
+    /// Customize the method that assigns the set fields to an existing instance. Only generated when set.
+    pub apply_method: Option<ApplyMethodSettings>,
+
+    /// Customize the protobuf-style `merge_from` method generated on the type itself. Only generated when set.
+    pub merge_from_method: Option<CommonDeclarationSettings>,
+
+    /// Customize the recipe type, that holds a finished builder and can build it repeatedly. Only generated when set.
+    pub recipe: Option<RecipeSettings>,
+
+    /// Customize the partial type, that has an optional field for every field. Only generated when set.
+    pub partial_type: Option<PartialTypeSettings>,
+
+    /// A context object the builder method takes, which is available to the defaults and the mutators.
+    pub context: Option<syn::Type>,
+
+    /// Fields whose declaration order - and therefore drop order - is checked to be as listed.
+    pub drop_order: Vec<syn::Ident>,
+
+    /// The maximal number of fields the struct may have.
+    pub max_fields: Option<syn::LitInt>,
+
+    /// Convenience constructors on the type itself, each taking the listed fields and using the
+    /// defaults for the rest.
+    pub constructors: Vec<(syn::Ident, Vec<syn::Ident>)>,
+
+    /// Conditions over the built value (as `self`) that the build methods check with `debug_assert!`.
+    pub invariants: Vec<syn::Expr>,
+
+    /// A function the build methods pass the fields to, in declaration order, instead of using a
+    /// struct literal.
+    pub build_via: Option<syn::ExprPath>,
+
+    /// A `bool` field that, when true, makes the build methods return an expression instead of
+    /// constructing the value.
+    pub short_circuit: Option<ShortCircuit>,
+
+    /// Generate `into_fresh_builder()` and `reset(self)`, that create a builder with no fields set.
+    pub reset: Option<Span>,
+
+    /// Generate a `SCHEMA` constant on the builder that describes its setters.
+    pub emit_schema: Option<Span>,
+
+    /// A configuration predicate that all the generated code (including the `builder()` method) is
+    /// gated behind.
+    pub cfg: Option<syn::Meta>,
+
+    /// A type defined elsewhere that the struct mirrors, which the build methods create instead.
+    pub remote: Option<syn::Path>,
+
     pub field_defaults: FieldBuilderAttr<'a>,
 
     pub crate_module_path: syn::Path,
 
     /// Functions that are able to mutate fields in the builder that are already set
     pub mutators: Vec<Mutator>,
+
+    /// Run the automatic mutators before the defaults are resolved, rather than after.
+    pub mutators_run_before_defaults: Option<Span>,
 }
 
 impl Default for TypeBuilderAttr<'_> {
@@ -126,9 +452,25 @@ impl Default for TypeBuilderAttr<'_> {
             builder_method: Default::default(),
             builder_type: Default::default(),
             build_method: Default::default(),
+            apply_method: Default::default(),
+            merge_from_method: Default::default(),
+            recipe: Default::default(),
+            partial_type: Default::default(),
+            context: Default::default(),
+            drop_order: Default::default(),
+            max_fields: Default::default(),
+            constructors: Default::default(),
+            invariants: Default::default(),
+            build_via: Default::default(),
+            short_circuit: Default::default(),
+            reset: Default::default(),
+            emit_schema: Default::default(),
+            cfg: Default::default(),
+            remote: Default::default(),
             field_defaults: Default::default(),
             crate_module_path: syn::parse_quote!(::typed_builder),
             mutators: Default::default(),
+            mutators_run_before_defaults: Default::default(),
         }
     }
 }
@@ -146,13 +488,16 @@ impl<'a> TypeBuilderAttr<'a> {
 
                     list
                 }
+                syn::Meta::NameValue(name_value) if path_to_single_string(&name_value.path).as_deref() == Some("builder") => {
+                    return Err(Error::new_spanned(attr, "expected `#[builder(...)]`"));
+                }
                 _ => continue,
             };
 
             result.apply_subsections(list)?;
         }
 
-        if result.builder_type.doc.is_some() || result.build_method.common.doc.is_some() {
+        if result.builder_type.common.doc.is_some() || result.build_method.common.doc.is_some() {
             result.doc = true;
         }
 
@@ -189,14 +534,210 @@ impl ApplyMeta for TypeBuilderAttr<'_> {
                 self.mutators.extend(expr.sub_attr()?.undelimited()?);
                 Ok(())
             }
+            "mutators_run" => {
+                let order = expr.key_value()?.parse_value::<syn::Ident>()?;
+                self.mutators_run_before_defaults = match order.to_string().as_str() {
+                    "before_defaults" => Some(order.span()),
+                    "after_defaults" => None,
+                    _ => {
+                        return Err(Error::new_spanned(
+                            order,
+                            "Unknown order - expected `before_defaults` or `after_defaults`",
+                        ))
+                    }
+                };
+                Ok(())
+            }
+            "into_all" => {
+                let mut span = self.field_defaults.setter.auto_into.as_ref().map(|auto_into| auto_into.span);
+                expr.apply_flag_to_field(&mut span, "converting all setter arguments with into()")?;
+                self.field_defaults.setter.auto_into = span.map(AutoInto::empty_spanned);
+                Ok(())
+            }
+            "context" => {
+                self.context = Some(expr.key_value()?.parse_value()?);
+                Ok(())
+            }
+            "max_fields" => {
+                let max_fields = expr.key_value()?.parse_value::<syn::LitInt>()?;
+                max_fields.base10_parse::<usize>()?;
+                self.max_fields = Some(max_fields);
+                Ok(())
+            }
+            "drop_order" => {
+                self.drop_order = expr_to_field_names(expr.key_value()?.parse_value()?)?;
+                Ok(())
+            }
+            "constructor" => {
+                for arg in expr.sub_attr()?.args::<AttrArg>()? {
+                    let sub = arg.sub_attr()?;
+                    let name = sub.name.clone();
+                    self.constructors
+                        .push((name, sub.args::<syn::Ident>()?.into_iter().collect()));
+                }
+                Ok(())
+            }
+            "reset" => expr.apply_flag_to_field(&mut self.reset, "resettable"),
+            "emit_schema" => expr.apply_flag_to_field(&mut self.emit_schema, "emitting a schema"),
+            "remote" => {
+                let remote = expr.key_value()?.parse_value::<syn::LitStr>()?;
+                self.remote = Some(remote.parse()?);
+                Ok(())
+            }
+            "cfg" => {
+                let predicate = expr.key_value()?.parse_value::<syn::LitStr>()?;
+                self.cfg = Some(predicate.parse()?);
+                Ok(())
+            }
+            "short_circuit" => {
+                let sub = expr.sub_attr()?;
+                let span = sub.name.span();
+                let mut short_circuit = ShortCircuit {
+                    span,
+                    field: None,
+                    returns: None,
+                };
+                short_circuit.apply_sub_attr(sub)?;
+                if short_circuit.field.is_none() || short_circuit.returns.is_none() {
+                    return Err(Error::new(
+                        span,
+                        "`short_circuit(...)` requires both `field = ...` and `returns = ...`",
+                    ));
+                }
+                self.short_circuit = Some(short_circuit);
+                Ok(())
+            }
+            "build_via" => {
+                self.build_via = Some(expr.key_value()?.parse_value()?);
+                Ok(())
+            }
+            "invariant" => {
+                self.invariants.push(expr.key_value()?.parse_value()?);
+                Ok(())
+            }
             "field_defaults" => self.field_defaults.apply_sub_attr(expr.sub_attr()?),
             "builder_method" => self.builder_method.apply_sub_attr(expr.sub_attr()?),
             "builder_type" => self.builder_type.apply_sub_attr(expr.sub_attr()?),
             "build_method" => self.build_method.apply_sub_attr(expr.sub_attr()?),
-            _ => Err(Error::new_spanned(
-                expr.name(),
-                format!("Unknown parameter {:?}", expr.name().to_string()),
-            )),
+            "apply_method" => match expr {
+                AttrArg::Flag(_) => {
+                    self.apply_method.get_or_insert_with(Default::default);
+                    Ok(())
+                }
+                AttrArg::Sub(sub) => self.apply_method.get_or_insert_with(Default::default).apply_sub_attr(sub),
+                AttrArg::Not { .. } => {
+                    self.apply_method = None;
+                    Ok(())
+                }
+                AttrArg::KeyValue(_) => Err(expr.incorrect_type()),
+            },
+            "recipe" => match expr {
+                AttrArg::Flag(_) => {
+                    self.recipe.get_or_insert_with(Default::default);
+                    Ok(())
+                }
+                AttrArg::Sub(sub) => self.recipe.get_or_insert_with(Default::default).apply_sub_attr(sub),
+                AttrArg::Not { .. } => {
+                    self.recipe = None;
+                    Ok(())
+                }
+                AttrArg::KeyValue(_) => Err(expr.incorrect_type()),
+            },
+            "partial_type" => match expr {
+                AttrArg::Flag(_) => {
+                    self.partial_type.get_or_insert_with(Default::default);
+                    Ok(())
+                }
+                AttrArg::Sub(sub) => self.partial_type.get_or_insert_with(Default::default).apply_sub_attr(sub),
+                AttrArg::Not { .. } => {
+                    self.partial_type = None;
+                    Ok(())
+                }
+                AttrArg::KeyValue(_) => Err(expr.incorrect_type()),
+            },
+            "merge_from_method" => match expr {
+                AttrArg::Flag(_) => {
+                    self.merge_from_method.get_or_insert_with(Default::default);
+                    Ok(())
+                }
+                AttrArg::Sub(sub) => self
+                    .merge_from_method
+                    .get_or_insert_with(Default::default)
+                    .apply_sub_attr(sub),
+                AttrArg::Not { .. } => {
+                    self.merge_from_method = None;
+                    Ok(())
+                }
+                AttrArg::KeyValue(_) => Err(expr.incorrect_type()),
+            },
+            _ => Err(expr.unknown_parameter(&[
+                "crate_module_path",
+                "doc",
+                "mutators",
+                "mutators_run",
+                "into_all",
+                "field_defaults",
+                "builder_method",
+                "builder_type",
+                "build_method",
+                "apply_method",
+                "merge_from_method",
+                "recipe",
+                "partial_type",
+                "context",
+                "drop_order",
+                "max_fields",
+                "constructor",
+                "invariant",
+                "build_via",
+                "short_circuit",
+                "reset",
+                "emit_schema",
+                "cfg",
+                "remote",
+            ])),
         }
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct ShortCircuit {
+    pub span: Span,
+    pub field: Option<syn::Ident>,
+    pub returns: Option<syn::Expr>,
+}
+
+impl ApplyMeta for ShortCircuit {
+    fn apply_meta(&mut self, expr: AttrArg) -> Result<(), Error> {
+        match expr.name().to_string().as_str() {
+            "field" => {
+                self.field = Some(expr.key_value()?.parse_value()?);
+                Ok(())
+            }
+            "returns" => {
+                self.returns = Some(expr.key_value()?.parse_value()?);
+                Ok(())
+            }
+            _ => Err(expr.unknown_parameter(&["field", "returns"])),
+        }
+    }
+}
+
+/// The arguments of `#[typed_builder_alias(Alias = Foo<...>)]` - a type alias of an instance of the
+/// struct, for which an alias of the builder type is declared as well.
+#[derive(Debug)]
+pub struct BuilderAlias {
+    pub ident: syn::Ident,
+    pub generics: syn::Generics,
+    pub ty: syn::TypePath,
+}
+
+impl syn::parse::Parse for BuilderAlias {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let ident = input.parse()?;
+        let generics = input.parse()?;
+        input.parse::<syn::Token![=]>()?;
+        let ty = input.parse()?;
+        Ok(Self { ident, generics, ty })
+    }
+}
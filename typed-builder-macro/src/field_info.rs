@@ -1,10 +1,18 @@
 use proc_macro2::{Ident, Span, TokenStream};
-use quote::quote_spanned;
-use syn::{parse::Error, spanned::Spanned};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::{
+    parse::{Error, Parser},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    Token,
+};
 
 use crate::mutator::Mutator;
 use crate::util::{expr_to_lit_string, ident_to_type, path_to_single_string, strip_raw_ident_prefix, ApplyMeta, AttrArg};
 
+/// The largest array that `setter(indexed)` generates a setter per element for.
+const MAX_INDEXED_LEN: usize = 32;
+
 #[derive(Debug)]
 pub struct FieldInfo<'a> {
     pub ordinal: usize,
@@ -12,6 +20,8 @@ pub struct FieldInfo<'a> {
     pub generic_ident: syn::Ident,
     pub ty: &'a syn::Type,
     pub builder_attr: FieldBuilderAttr<'a>,
+    /// For `try_into(deferred)` setters, the error type of the build method.
+    pub deferred_error: Option<syn::Type>,
 }
 
 impl<'a> FieldInfo<'a> {
@@ -23,6 +33,7 @@ impl<'a> FieldInfo<'a> {
                 generic_ident: syn::Ident::new(&format!("__{}", strip_raw_ident_prefix(name.to_string())), Span::call_site()),
                 ty: &field.ty,
                 builder_attr: field_defaults.with(name, &field.attrs)?,
+                deferred_error: None,
             }
             .post_process()
         } else {
@@ -38,9 +49,20 @@ impl<'a> FieldInfo<'a> {
         ident_to_type(self.generic_ident.clone())
     }
 
+    /// The type of the value the builder stores for the field - a `Result` for deferred `try_into`
+    /// setters, whose error is returned by the build method.
+    pub fn stored_type(&self) -> syn::Type {
+        if let Some(error_type) = &self.deferred_error {
+            let ty = self.ty;
+            syn::parse_quote!(::core::result::Result<#ty, #error_type>)
+        } else {
+            self.ty.clone()
+        }
+    }
+
     pub fn tuplized_type_ty_param(&self) -> syn::Type {
         let mut types = syn::punctuated::Punctuated::default();
-        types.push(self.ty.clone());
+        types.push(self.stored_type());
         types.push_punct(Default::default());
         syn::TypeTuple {
             paren_token: Default::default(),
@@ -50,6 +72,83 @@ impl<'a> FieldInfo<'a> {
     }
 
     pub fn type_from_inside_option(&self) -> Option<&syn::Type> {
+        self.type_from_inside_generic("Option")
+    }
+
+    /// The type `strip_option` setters take - the type inside the `Option`, or inside the custom
+    /// option-like type of `strip_option(ty = ...)`.
+    pub fn stripped_option_type(&self) -> Result<&syn::Type, Error> {
+        if let Some(custom) = &self.builder_attr.setter.strip_option_custom {
+            let ty = custom.ty.as_ref().expect("checked when parsing");
+            let name = ty.segments.last().expect("paths are not empty").ident.to_string();
+            self.type_from_inside_generic(&name)
+                .ok_or_else(|| Error::new_spanned(self.ty, format!("can't `strip_option` - field is not `{}<...>`", name)))
+        } else {
+            self.type_from_inside_option()
+                .ok_or_else(|| Error::new_spanned(self.ty, "can't `strip_option` - field is not `Option<...>`"))
+        }
+    }
+
+    /// Wraps the value of a `strip_option` setter in `Some` - or in the `some` constructor of
+    /// `strip_option(ty = ...)`.
+    fn wrap_stripped_option(&self, value: TokenStream) -> TokenStream {
+        if let Some(custom) = &self.builder_attr.setter.strip_option_custom {
+            let some = custom.some.as_ref().expect("checked when parsing");
+            quote!(#some(#value))
+        } else {
+            quote!(::core::option::Option::Some(#value))
+        }
+    }
+
+    pub fn type_from_inside_vec(&self) -> Option<&syn::Type> {
+        self.type_from_inside_generic("Vec")
+    }
+
+    /// Whether the field is one of the standard collections, which `merge_from_method` extends
+    /// instead of overwriting.
+    pub fn is_std_collection(&self) -> bool {
+        const COLLECTIONS: &[&str] = &[
+            "Vec",
+            "VecDeque",
+            "LinkedList",
+            "HashMap",
+            "BTreeMap",
+            "HashSet",
+            "BTreeSet",
+            "BinaryHeap",
+        ];
+        let syn::Type::Path(type_path) = self.ty else {
+            return false;
+        };
+        type_path.qself.is_none()
+            && type_path.path.segments.last().is_some_and(|segment| {
+                matches!(segment.arguments, syn::PathArguments::AngleBracketed(_))
+                    && COLLECTIONS.iter().any(|collection| segment.ident == collection)
+            })
+    }
+
+    fn key_and_value_types_from_inside_map(&self) -> Option<(&syn::Type, &syn::Type)> {
+        let syn::Type::Path(type_path) = self.ty else {
+            return None;
+        };
+        if type_path.qself.is_some() {
+            return None;
+        }
+        let segment = type_path.path.segments.last()?;
+        if segment.ident != "HashMap" && segment.ident != "BTreeMap" {
+            return None;
+        }
+        let syn::PathArguments::AngleBracketed(generic_params) = &segment.arguments else {
+            return None;
+        };
+        let mut types = generic_params.args.iter().filter_map(|arg| match arg {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        });
+        Some((types.next()?, types.next()?))
+    }
+
+    fn type_from_inside_generic(&self, generic_name: &str) -> Option<&syn::Type> {
         let path = if let syn::Type::Path(type_path) = self.ty {
             if type_path.qself.is_some() {
                 return None;
@@ -59,7 +158,7 @@ impl<'a> FieldInfo<'a> {
             return None;
         };
         let segment = path.segments.last()?;
-        if segment.ident != "Option" {
+        if segment.ident != generic_name {
             return None;
         }
         let generic_params = if let syn::PathArguments::AngleBracketed(generic_params) = &segment.arguments {
@@ -74,10 +173,49 @@ impl<'a> FieldInfo<'a> {
         }
     }
 
+    /// For `Box<dyn Trait + ...>` fields, the path to `Box` and the bounds of the trait object.
+    pub fn type_from_inside_box_dyn(&self) -> Option<(syn::Path, TokenStream)> {
+        let path = if let syn::Type::Path(type_path) = self.ty {
+            if type_path.qself.is_some() {
+                return None;
+            }
+            &type_path.path
+        } else {
+            return None;
+        };
+        let segment = path.segments.last()?;
+        if segment.ident != "Box" {
+            return None;
+        }
+        let generic_params = if let syn::PathArguments::AngleBracketed(generic_params) = &segment.arguments {
+            generic_params
+        } else {
+            return None;
+        };
+        let trait_object =
+            if let syn::GenericArgument::Type(syn::Type::TraitObject(trait_object)) = generic_params.args.first()? {
+                trait_object
+            } else {
+                return None;
+            };
+        let mut box_path = path.clone();
+        box_path.segments.last_mut()?.arguments = syn::PathArguments::None;
+        let bounds = &trait_object.bounds;
+        // Like `dyn Trait` inside a `Box`, the argument must be `'static` unless a lifetime is given.
+        let bounds = if bounds.iter().any(|bound| matches!(bound, syn::TypeParamBound::Lifetime(_))) {
+            quote!(#bounds)
+        } else {
+            quote!(#bounds + 'static)
+        };
+        Some((box_path, bounds))
+    }
+
     pub fn setter_method_name(&self) -> Ident {
         let name = strip_raw_ident_prefix(self.name.to_string());
 
-        if let (Some(prefix), Some(suffix)) = (&self.builder_attr.setter.prefix, &self.builder_attr.setter.suffix) {
+        if let Some(name) = &self.builder_attr.setter.name {
+            name.clone()
+        } else if let (Some(prefix), Some(suffix)) = (&self.builder_attr.setter.prefix, &self.builder_attr.setter.suffix) {
             Ident::new(&format!("{}{}{}", prefix, name, suffix), Span::call_site())
         } else if let Some(prefix) = &self.builder_attr.setter.prefix {
             Ident::new(&format!("{}{}", prefix, name), Span::call_site())
@@ -88,7 +226,449 @@ impl<'a> FieldInfo<'a> {
         }
     }
 
+    pub fn field_mut_method_name(&self) -> Ident {
+        Ident::new(&format!("{}_mut", self.setter_method_name()), Span::call_site())
+    }
+
+    pub fn maybe_into_method_name(&self) -> Ident {
+        Ident::new(&format!("{}_into", self.setter_method_name()), Span::call_site())
+    }
+
+    pub fn unit_method_name(&self, unit: &UnitSetter) -> Ident {
+        Ident::new(&format!("{}_{}", self.setter_method_name(), unit.name), Span::call_site())
+    }
+
+    /// The parameter and the value of the additional setter of `unit = "..."`, that passes a
+    /// quantity in that unit to the unit's constructor.
+    pub fn unit_param_list_and_value(&self, unit: &UnitSetter) -> Result<(TokenStream, TokenStream), Error> {
+        let field_name = self.name;
+        let arg_type = &unit.arg_type;
+        let target_type = if self.builder_attr.setter.strip_option.is_some() {
+            self.stripped_option_type()?
+        } else {
+            self.ty
+        };
+        let value = if let Some(via) = &unit.via {
+            quote!(#via(#field_name))
+        } else {
+            let constructor = format_ident!("from_{}", unit.name);
+            quote!(<#target_type>::#constructor(#field_name))
+        };
+        let value = if self.builder_attr.setter.strip_option.is_some() {
+            self.wrap_stripped_option(value)
+        } else {
+            value
+        };
+        Ok((quote!(#field_name: #arg_type), value))
+    }
+
+    /// The capability token parameter that all the setters of the field take first, if it requires one.
+    pub fn token_param(&self) -> TokenStream {
+        if let Some(token) = &self.builder_attr.setter.requires_token {
+            quote!(_: #token,)
+        } else {
+            quote!()
+        }
+    }
+
+    /// The `async` keyword of the setter, for `async_into` fields.
+    pub fn setter_asyncness(&self) -> TokenStream {
+        if let Some(async_into) = self.builder_attr.setter.async_into {
+            quote_spanned!(async_into => async)
+        } else {
+            quote!()
+        }
+    }
+
+    /// Wraps the value of a setter with the `clamp` of the field, if it has one.
+    fn clamped(&self, arg_type: &syn::Type, value: TokenStream) -> TokenStream {
+        // `<T>::clamp` resolves to the inherent method of floats, and to `Ord::clamp` for other types.
+        if let Some(Clamp {
+            min: Some(min),
+            max: Some(max),
+            ..
+        }) = &self.builder_attr.setter.clamp
+        {
+            quote!(<#arg_type>::clamp(#value, #min, #max))
+        } else {
+            value
+        }
+    }
+
+    /// The parameter and the value of the additional `_into` setter of `maybe_into` fields.
+    pub fn maybe_into_param_list_and_value(&self) -> Result<(TokenStream, TokenStream), Error> {
+        let field_name = self.name;
+        if self.builder_attr.setter.strip_option.is_some() {
+            let arg_type = self.stripped_option_type()?;
+            let value = self.clamped(arg_type, quote!(::core::convert::Into::<#arg_type>::into(#field_name)));
+            Ok((
+                quote!(#field_name: impl ::core::convert::Into<#arg_type>),
+                self.wrap_stripped_option(value),
+            ))
+        } else {
+            let arg_type = self.ty;
+            Ok((
+                quote!(#field_name: impl ::core::convert::Into<#arg_type>),
+                self.clamped(arg_type, quote!(::core::convert::Into::<#arg_type>::into(#field_name))),
+            ))
+        }
+    }
+
+    /// The named generic parameter of `into(generic = "...")` setters.
+    pub fn named_into_generic(&self) -> Result<Option<TokenStream>, Error> {
+        let Some(auto_into) = &self.builder_attr.setter.auto_into else {
+            return Ok(None);
+        };
+        let Some(generic) = &auto_into.generic else {
+            return Ok(None);
+        };
+        let first_hop = if let Some(first_hop) = auto_into.via.first() {
+            first_hop
+        } else if self.builder_attr.setter.strip_option.is_some() {
+            self.stripped_option_type()?
+        } else {
+            self.ty
+        };
+        Ok(Some(
+            quote_spanned!(self.ty.span() => <#generic: ::core::convert::Into<#first_hop>>),
+        ))
+    }
+
+    /// For fallible (`try_into` and `parse`) setters, the generic parameter of the setter - if it
+    /// needs one - and its error type.
+    pub fn fallible_setter_generic_and_error(&self) -> Result<Option<(Option<TokenStream>, TokenStream)>, Error> {
+        let setter = &self.builder_attr.setter;
+        if setter.try_into.is_none() && setter.parse.is_none() || self.deferred_error.is_some() {
+            return Ok(None);
+        }
+        let arg_type = if setter.strip_option.is_some() {
+            self.stripped_option_type()?
+        } else {
+            self.ty
+        };
+        Ok(Some(if setter.try_into.is_some() {
+            (
+                Some(quote!(<__TryIntoValue: ::core::convert::TryInto<#arg_type>>)),
+                quote!(<__TryIntoValue as ::core::convert::TryInto<#arg_type>>::Error),
+            )
+        } else {
+            (None, quote!(<#arg_type as ::core::str::FromStr>::Err))
+        }))
+    }
+
+    /// For deferred `try_into` setters, the generic parameter of the setter, whose conversion error
+    /// must convert into the error type of the build method.
+    pub fn deferred_try_into_generic(&self) -> Result<Option<TokenStream>, Error> {
+        let Some(error_type) = &self.deferred_error else {
+            return Ok(None);
+        };
+        let arg_type = if self.builder_attr.setter.strip_option.is_some() {
+            self.stripped_option_type()?
+        } else {
+            self.ty
+        };
+        Ok(Some(quote!(<
+            __TryIntoValue: ::core::convert::TryInto<#arg_type, Error = __TryIntoError>,
+            __TryIntoError: ::core::convert::Into<#error_type>,
+        >)))
+    }
+
+    /// For `each` setters, the parameters of the setter and the expression of the item it adds.
+    pub fn each_setter_param_list_and_item(&self) -> Result<Option<(TokenStream, TokenStream)>, Error> {
+        let Some(each) = &self.builder_attr.setter.each else {
+            return Ok(None);
+        };
+        let item_type = self
+            .type_from_inside_vec()
+            .ok_or_else(|| Error::new_spanned(self.ty, "can't `each` - field is not `Vec<...>`"))?;
+        let param_type = |ty: &syn::Type| {
+            if each.into.is_some() {
+                quote!(impl ::core::convert::Into<#ty>)
+            } else {
+                ty.to_token_stream()
+            }
+        };
+        let param_value = |param: &Ident| {
+            if each.into.is_some() {
+                quote!(::core::convert::Into::into(#param))
+            } else {
+                param.to_token_stream()
+            }
+        };
+        Ok(Some(if let syn::Type::Tuple(tuple) = item_type {
+            let params = (0..tuple.elems.len()).map(|i| format_ident!("item{}", i)).collect::<Vec<_>>();
+            let param_list = params.iter().zip(&tuple.elems).map(|(param, ty)| {
+                let ty = param_type(ty);
+                quote!(#param: #ty)
+            });
+            let values = params.iter().map(param_value);
+            (quote!(#(#param_list),*), quote!((#(#values,)*)))
+        } else {
+            let param = format_ident!("item");
+            let ty = param_type(item_type);
+            let value = param_value(&param);
+            (quote!(#param: #ty), value)
+        }))
+    }
+
+    /// For `indexed` setters, the type and the number of the elements of the array.
+    pub fn indexed_setter_item_and_len(&self) -> Result<Option<(&syn::Type, usize)>, Error> {
+        let Some(indexed) = self.builder_attr.setter.indexed else {
+            return Ok(None);
+        };
+        let syn::Type::Array(array) = self.ty else {
+            return Err(Error::new_spanned(self.ty, "can't `indexed` - field is not `[T; N]`"));
+        };
+        let syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(len), ..
+        }) = &array.len
+        else {
+            return Err(Error::new_spanned(
+                &array.len,
+                "can't `indexed` - the length of the array must be an integer literal",
+            ));
+        };
+        let len = len.base10_parse::<usize>()?;
+        if !(1..=MAX_INDEXED_LEN).contains(&len) {
+            return Err(Error::new(
+                indexed,
+                format!("`indexed` supports arrays of 1 to {} elements", MAX_INDEXED_LEN),
+            ));
+        }
+        Ok(Some((&array.elem, len)))
+    }
+
+    /// For `entry` setters, the parameters of the setter and the expressions of the key and the value
+    /// it inserts.
+    pub fn entry_setter_param_list_key_and_value(&self) -> Result<Option<(TokenStream, TokenStream, TokenStream)>, Error> {
+        let Some(entry) = &self.builder_attr.setter.entry else {
+            return Ok(None);
+        };
+        let (key_type, value_type) = self
+            .key_and_value_types_from_inside_map()
+            .ok_or_else(|| Error::new_spanned(self.ty, "can't `entry` - field is not `HashMap<...>` or `BTreeMap<...>`"))?;
+        Ok(Some(if entry.into.is_some() {
+            (
+                quote!(key: impl ::core::convert::Into<#key_type>, value: impl ::core::convert::Into<#value_type>),
+                quote!(::core::convert::Into::into(key)),
+                quote!(::core::convert::Into::into(value)),
+            )
+        } else {
+            (quote!(key: #key_type, value: #value_type), quote!(key), quote!(value))
+        }))
+    }
+
+    pub fn setter_fn_method_name(&self) -> Ident {
+        Ident::new(&format!("{}_setter", self.setter_method_name()), Span::call_site())
+    }
+
+    /// The argument types of the function value returned by the `setter_fn` method. Unlike the
+    /// setter itself, it cannot use `impl Trait` arguments - so `into` setters take the target type.
+    pub fn setter_fn_arg_types(&self) -> Result<Vec<TokenStream>, Error> {
+        let setter = &self.builder_attr.setter;
+        if let Some(span) = (setter.transform.as_ref().map(|t| t.span))
+            .or(setter.boxed)
+            .or(setter.try_into)
+            .or(setter.parse)
+        {
+            return Err(Error::new(
+                span,
+                "`setter_fn` cannot be combined with `transform`, `boxed`, `try_into` or `parse`",
+            ));
+        }
+        if setter.strip_bool.is_some() {
+            return Ok(vec![]);
+        }
+        if setter.format.is_some() {
+            return Ok(vec![quote!(::core::fmt::Arguments<'_>)]);
+        }
+        if setter.from_slice.is_some() {
+            let item_type = self
+                .type_from_inside_vec()
+                .ok_or_else(|| Error::new_spanned(self.ty, "can't `from_slice` - field is not `Vec<...>`"))?;
+            return Ok(vec![quote!(&[#item_type])]);
+        }
+        let arg_type = if setter.strip_option.is_some() {
+            self.stripped_option_type()?
+        } else {
+            self.ty
+        };
+        Ok(vec![
+            match setter.auto_into.as_ref().and_then(|auto_into| auto_into.via.first()) {
+                Some(first_hop) => first_hop.to_token_stream(),
+                None => arg_type.to_token_stream(),
+            },
+        ])
+    }
+
+    /// The parameters of the setter method and the expression that converts them to the field's value.
+    pub fn setter_param_list_and_value(&self, crate_module_path: &syn::Path) -> Result<(TokenStream, TokenStream), Error> {
+        let field_name = self.name;
+
+        // NOTE: both auto_into and strip_option affect `arg_type` and `arg_expr`, but the order of
+        // nesting is different so we have to do this little dance.
+        let arg_type = if self.builder_attr.setter.strip_option.is_some() && self.builder_attr.setter.transform.is_none() {
+            self.stripped_option_type()?
+        } else {
+            self.ty
+        };
+        let value_type = arg_type;
+        if self.deferred_error.is_some() {
+            let value = if self.builder_attr.setter.strip_option.is_some() {
+                self.wrap_stripped_option(quote!(value))
+            } else {
+                quote!(value)
+            };
+            return Ok((
+                quote!(#field_name: __TryIntoValue),
+                quote! {
+                    match ::core::convert::TryInto::<#arg_type>::try_into(#field_name) {
+                        ::core::result::Result::Ok(value) => ::core::result::Result::Ok(#value),
+                        ::core::result::Result::Err(error) => ::core::result::Result::Err(::core::convert::Into::into(error)),
+                    }
+                },
+            ));
+        }
+        let (arg_type, arg_expr) = if self.builder_attr.setter.format.is_some() {
+            let formatted = quote!(::std::fmt::format(#field_name));
+            let arg_expr = if self.builder_attr.setter.auto_into.is_some() {
+                quote!(::core::convert::Into::<#arg_type>::into(#formatted))
+            } else if is_string(arg_type) {
+                formatted
+            } else {
+                return Err(Error::new_spanned(
+                    arg_type,
+                    "can't `format` - field is not `String` (use `into` for types that convert from `String`)",
+                ));
+            };
+            (quote!(::core::fmt::Arguments<'_>), arg_expr)
+        } else if self.builder_attr.setter.take.is_some() {
+            (quote!(&mut #arg_type), quote!(::core::mem::take::<#arg_type>(#field_name)))
+        } else if self.builder_attr.setter.async_into.is_some() {
+            (
+                quote!(impl ::core::future::IntoFuture<Output = #arg_type>),
+                quote!(::core::future::IntoFuture::into_future(#field_name).await),
+            )
+        } else if let Some(auto_into) = &self.builder_attr.setter.auto_into {
+            let first_hop = auto_into.via.first().unwrap_or(arg_type);
+            // Spanned to the field's type, so that errors about a missing `Into` impl point at the field
+            // rather than at the derive.
+            let span = self.ty.span();
+            let param_type = if let Some(generic) = &auto_into.generic {
+                generic.to_token_stream()
+            } else {
+                quote_spanned!(span => impl ::core::convert::Into<#first_hop>)
+            };
+            if auto_into.via.is_empty() {
+                (
+                    param_type,
+                    quote_spanned!(span => ::core::convert::Into::<#arg_type>::into(#field_name)),
+                )
+            } else {
+                let arg_expr = auto_into.via.iter().chain(Some(arg_type)).fold(
+                    field_name.to_token_stream(),
+                    |expr, hop| quote_spanned!(span => ::core::convert::Into::<#hop>::into(#expr)),
+                );
+                (param_type, arg_expr)
+            }
+        } else if self.builder_attr.setter.try_into.is_some() {
+            (
+                quote!(__TryIntoValue),
+                quote!(::core::convert::TryInto::<#arg_type>::try_into(#field_name)?),
+            )
+        } else if self.builder_attr.setter.parse.is_some() {
+            (
+                quote!(&str),
+                quote!(<#arg_type as ::core::str::FromStr>::from_str(#field_name)?),
+            )
+        } else if self.builder_attr.setter.number_cast.is_some() {
+            if !is_primitive_number(arg_type) {
+                return Err(Error::new_spanned(
+                    arg_type,
+                    "can't `number_cast` - field is not a primitive numeric type",
+                ));
+            }
+            (
+                quote!(impl #crate_module_path::NumberCast<#arg_type>),
+                quote!(#crate_module_path::NumberCast::number_cast(#field_name)),
+            )
+        } else {
+            (arg_type.to_token_stream(), field_name.to_token_stream())
+        };
+
+        let arg_expr = self.clamped(value_type, arg_expr);
+
+        Ok(if self.builder_attr.setter.strip_bool.is_some() {
+            (quote!(), quote!(true))
+        } else if self.builder_attr.setter.from_slice.is_some() {
+            let item_type = self
+                .type_from_inside_vec()
+                .ok_or_else(|| Error::new_spanned(self.ty, "can't `from_slice` - field is not `Vec<...>`"))?;
+            if self.builder_attr.setter.auto_into.is_some() {
+                let field_type = self.ty;
+                (
+                    quote!(#field_name: &[impl ::core::clone::Clone + ::core::convert::Into<#item_type>]),
+                    quote!(#field_name.iter().cloned().map(::core::convert::Into::into).collect::<#field_type>()),
+                )
+            } else {
+                (
+                    quote!(#field_name: &[#item_type]),
+                    quote!(<[#item_type]>::to_vec(#field_name)),
+                )
+            }
+        } else if self.builder_attr.setter.from_fn.is_some() {
+            let field_type = self.ty;
+            (
+                quote!(#field_name: impl ::core::ops::FnMut() -> ::core::option::Option<<#field_type as ::core::iter::IntoIterator>::Item>),
+                quote!(::core::iter::Iterator::collect::<#field_type>(::core::iter::from_fn(#field_name))),
+            )
+        } else if self.builder_attr.setter.extend.is_some() {
+            let field_type = self.ty;
+            (
+                quote!(#field_name: impl ::core::iter::IntoIterator<Item = <#field_type as ::core::iter::IntoIterator>::Item>),
+                quote!(::core::iter::Iterator::collect::<#field_type>(::core::iter::IntoIterator::into_iter(#field_name))),
+            )
+        } else if self.builder_attr.setter.boxed.is_some() {
+            let (box_path, bounds) = self
+                .type_from_inside_box_dyn()
+                .ok_or_else(|| Error::new_spanned(self.ty, "can't `boxed` - field is not `Box<dyn ...>`"))?;
+            let field_type = self.ty;
+            (
+                quote!(#field_name: impl #bounds),
+                quote!(#box_path::new(#field_name) as #field_type),
+            )
+        } else if let Some(transform) = &self.builder_attr.setter.transform {
+            let params = transform.params.iter().map(|(pat, ty)| quote!(#pat: #ty));
+            let body = &transform.body;
+            (quote!(#(#params),*), quote!({ #body }))
+        } else if self.builder_attr.setter.strip_option.is_some() {
+            (quote!(#field_name: #arg_type), self.wrap_stripped_option(arg_expr))
+        } else {
+            (quote!(#field_name: #arg_type), arg_expr)
+        })
+    }
+
     fn post_process(mut self) -> Result<Self, Error> {
+        if let (Some(span), Some(default)) = (self.builder_attr.default_once, &self.builder_attr.default) {
+            self.builder_attr.default_once_expr = Some(default.clone());
+            let ty = self.ty;
+            self.builder_attr.default = Some(syn::parse2(quote_spanned!(span => {
+                static DEFAULT: ::std::sync::OnceLock<#ty> = ::std::sync::OnceLock::new();
+                ::core::clone::Clone::clone(DEFAULT.get_or_init(|| #default))
+            }))?);
+        }
+        // The default of a `lazy` field is not computed here - it becomes the init closure of the
+        // field's lazy type, e.g. `LazyLock<T>`.
+        if let (Some(span), Some(default)) = (self.builder_attr.setter.lazy, &self.builder_attr.default) {
+            let ty = self.ty;
+            self.builder_attr.default = Some(syn::parse2(quote_spanned!(span => <#ty>::new(|| #default)))?);
+        }
+        // Like `Option` fields usually are, fields of a custom option-like type are optional.
+        if let Some(custom) = &self.builder_attr.setter.strip_option_custom {
+            if self.builder_attr.default.is_none() {
+                self.builder_attr.default = custom.none.clone();
+            }
+        }
         if let Some(ref strip_bool_span) = self.builder_attr.setter.strip_bool {
             if let Some(default_span) = self.builder_attr.default.as_ref().map(Spanned::span) {
                 let mut error = Error::new(
@@ -119,22 +699,59 @@ pub struct FieldBuilderAttr<'a> {
     /// Functions that are able to mutate fields in the builder that are already set
     pub mutators: Vec<Mutator>,
     pub mutable_during_default_resolution: Option<Span>,
+    pub computed: Option<Span>,
+    pub default_once: Option<Span>,
+    /// The expression of `default_once`, before it is wrapped in the `OnceLock` that `default` gets.
+    pub default_once_expr: Option<syn::Expr>,
+    /// Whether `default` is a `Result` that the build method resolves with `?`.
+    pub default_try: Option<Span>,
+    /// Replaces the deprecation note shown when building without setting this field.
+    pub missing_message: Option<syn::LitStr>,
+    /// Show the field as `***` in the `Debug` impl of the builder type.
+    pub secret: Option<Span>,
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct SetterSettings {
     pub doc: Option<syn::Expr>,
     pub skip: Option<Span>,
-    pub auto_into: Option<Span>,
+    pub lazy: Option<Span>,
+    pub auto_into: Option<AutoInto>,
     pub strip_option: Option<Span>,
+    pub strip_option_custom: Option<StripOptionCustom>,
     pub strip_bool: Option<Span>,
+    pub boxed: Option<Span>,
+    pub field_mut: Option<Span>,
+    pub from_slice: Option<Span>,
+    pub from_fn: Option<Span>,
+    pub take: Option<Span>,
+    pub setter_fn: Option<Span>,
+    pub number_cast: Option<Span>,
+    pub try_into: Option<Span>,
+    /// Whether the conversion of `try_into` is deferred to the build method.
+    pub try_into_deferred: Option<Span>,
+    pub parse: Option<Span>,
+    pub each: Option<EachSetter>,
+    pub entry: Option<EntrySetter>,
+    pub indexed: Option<Span>,
+    pub maybe_into: Option<Span>,
+    pub async_into: Option<Span>,
+    pub units: Vec<UnitSetter>,
+    pub clamp: Option<Clamp>,
+    pub requires_token: Option<syn::Type>,
+    pub merge: Option<Span>,
+    pub extend: Option<Span>,
+    pub format: Option<Span>,
     pub transform: Option<Transform>,
     pub prefix: Option<String>,
     pub suffix: Option<String>,
+    /// Replaces the setter's name, including any `prefix` and `suffix`.
+    pub name: Option<Ident>,
 }
 
 impl<'a> FieldBuilderAttr<'a> {
     pub fn with(mut self, name: &Ident, attrs: &'a [syn::Attribute]) -> Result<Self, Error> {
+        let mut field_settings = Vec::new();
         for attr in attrs {
             let list = match &attr.meta {
                 syn::Meta::List(list) => {
@@ -154,17 +771,50 @@ impl<'a> FieldBuilderAttr<'a> {
                     list
                 }
                 syn::Meta::Path(path) | syn::Meta::NameValue(syn::MetaNameValue { path, .. }) => {
-                    if path_to_single_string(path).as_deref() == Some("deprecated") {
-                        self.deprecated = Some(attr);
-                    };
+                    match path_to_single_string(path).as_deref() {
+                        Some("deprecated") => self.deprecated = Some(attr),
+                        Some("builder") if matches!(attr.meta, syn::Meta::NameValue(_)) => {
+                            return Err(Error::new_spanned(attr, "expected `#[builder(...)]`"));
+                        }
+                        _ => {}
+                    }
 
                     continue;
                 }
             };
 
+            if let Ok(args) = Punctuated::<AttrArg, Token![,]>::parse_terminated.parse2(list.tokens.clone()) {
+                field_settings.extend(args.iter().map(|arg| arg.name().clone()));
+            }
             self.apply_subsections(list)?;
         }
 
+        if self.computed.is_some() {
+            // Only settings written on the field itself conflict with `computed` - the ones inherited
+            // from `field_defaults` are simply irrelevant for a field that has no setter.
+            let conflicting_settings = [
+                "default",
+                "default_code",
+                "default_once",
+                "setter",
+                "via_mutators",
+                "mutators",
+            ];
+            if let Some(setting) = field_settings
+                .iter()
+                .find(|setting| conflicting_settings.iter().any(|conflicting| setting == conflicting))
+            {
+                return Err(Error::new_spanned(
+                    setting,
+                    format!("{:?} cannot be used together with `computed`", setting.to_string()),
+                ));
+            }
+            self.setter = SetterSettings {
+                skip: self.computed,
+                ..Default::default()
+            };
+        }
+
         for mutator in self.mutators.iter_mut() {
             mutator.required_fields.insert(name.clone());
         }
@@ -182,10 +832,221 @@ impl<'a> FieldBuilderAttr<'a> {
             ));
         }
 
+        if let (Some(lazy), None) = (&self.setter.lazy, &self.setter.skip) {
+            return Err(Error::new(*lazy, "#[builder(setter(lazy))] must be accompanied by skip"));
+        }
+
+        let setter = &self.setter;
+        let into = setter.auto_into.as_ref().map(|auto_into| auto_into.span);
+        let clamp = setter.clamp.as_ref().map(|clamp| clamp.span);
+        let transform = setter.transform.as_ref().map(|transform| transform.span);
+        // The settings that decide the setter's argument and how it becomes the field's value. `into`
+        // is not rejected with `strip_bool` and `transform`, which ignore it, so that
+        // `field_defaults(setter(into))` can apply to all the fields of a struct that has them.
+        let conversions = [
+            ("into", into),
+            ("try_into", setter.try_into),
+            ("parse", setter.parse),
+            ("number_cast", setter.number_cast),
+            ("strip_bool", setter.strip_bool),
+            ("boxed", setter.boxed),
+            ("from_slice", setter.from_slice),
+            ("format", setter.format),
+            ("transform", transform),
+        ];
+
+        for (fallible_caption, fallible_span) in [("try_into", setter.try_into), ("parse", setter.parse)] {
+            if let Some(fallible_span) = fallible_span {
+                let conflicting = conversions
+                    .iter()
+                    .filter(|(caption, _)| *caption != fallible_caption)
+                    .copied()
+                    .collect::<Vec<_>>();
+                reject_conflicts(fallible_caption, fallible_span, &conflicting)?;
+            }
+        }
+        if let Some(boxed) = setter.boxed {
+            reject_conflicts("boxed", boxed, &[("into", into)])?;
+        }
+        if let Some(number_cast) = setter.number_cast {
+            reject_conflicts("number_cast", number_cast, &[("into", into), ("format", setter.format)])?;
+        }
+
+        if let Some(deferred) = setter.try_into_deferred {
+            reject_conflicts(
+                "try_into(deferred)",
+                deferred,
+                &[
+                    ("skip", setter.skip),
+                    (
+                        "via_mutators",
+                        self.via_mutators.as_ref().map(|via_mutators| via_mutators.span),
+                    ),
+                    ("field_mut", setter.field_mut),
+                    ("setter_fn", setter.setter_fn),
+                    ("each", setter.each.as_ref().map(|each| each.span)),
+                    ("entry", setter.entry.as_ref().map(|entry| entry.span)),
+                    ("units", setter.units.first().map(|unit| unit.name.span())),
+                    ("clamp", clamp),
+                ],
+            )?;
+        }
+
+        // Only the build method can return the error of a `default_try`.
+        if let Some(default_try) = self.default_try {
+            reject_conflicts(
+                "default_try",
+                default_try,
+                &[
+                    ("lazy", setter.lazy),
+                    ("each", setter.each.as_ref().map(|each| each.span)),
+                    ("entry", setter.entry.as_ref().map(|entry| entry.span)),
+                    ("try_into(deferred)", setter.try_into_deferred),
+                ],
+            )?;
+        }
+
+        if let Some(indexed) = setter.indexed {
+            reject_conflicts(
+                "indexed",
+                indexed,
+                &[
+                    ("default", self.default.as_ref().map(Spanned::span)),
+                    ("default_once", self.default_once),
+                    (
+                        "via_mutators",
+                        self.via_mutators.as_ref().map(|via_mutators| via_mutators.span),
+                    ),
+                    ("try_into(deferred)", setter.try_into_deferred),
+                ],
+            )?;
+        }
+
+        if let Some(maybe_into) = setter.maybe_into {
+            reject_conflicts("maybe_into", maybe_into, &conversions)?;
+        }
+
+        if let Some(async_into) = setter.async_into {
+            let conflicting = [
+                ("maybe_into", setter.maybe_into),
+                ("merge", setter.merge),
+                ("setter_fn", setter.setter_fn),
+            ];
+            reject_conflicts("async_into", async_into, &[&conversions[..], &conflicting].concat())?;
+        }
+
+        if let Some(take) = setter.take {
+            let conflicting = [
+                ("from_fn", setter.from_fn),
+                ("maybe_into", setter.maybe_into),
+                ("async_into", setter.async_into),
+                ("setter_fn", setter.setter_fn),
+            ];
+            reject_conflicts("take", take, &[&conversions[..], &conflicting].concat())?;
+        }
+
+        if let Some(from_fn) = setter.from_fn {
+            reject_conflicts(
+                "from_fn",
+                from_fn,
+                &[
+                    ("into", into),
+                    ("try_into", setter.try_into),
+                    ("parse", setter.parse),
+                    ("number_cast", setter.number_cast),
+                    ("clamp", clamp),
+                    ("maybe_into", setter.maybe_into),
+                    ("async_into", setter.async_into),
+                    ("setter_fn", setter.setter_fn),
+                ],
+            )?;
+        }
+
+        if let Some(extend) = setter.extend {
+            reject_conflicts(
+                "extend",
+                extend,
+                &[
+                    ("into", into),
+                    ("try_into", setter.try_into),
+                    ("parse", setter.parse),
+                    ("number_cast", setter.number_cast),
+                    ("clamp", clamp),
+                    ("take", setter.take),
+                    ("maybe_into", setter.maybe_into),
+                    ("async_into", setter.async_into),
+                    ("merge", setter.merge),
+                    ("setter_fn", setter.setter_fn),
+                ],
+            )?;
+        }
+
+        // `from_slice`, `from_fn` and `transform` produce a value of the field's type, which `merge`
+        // extends the set value with like any other - so they are not listed here.
+        if let Some(merge) = setter.merge {
+            reject_conflicts(
+                "merge",
+                merge,
+                &[
+                    ("strip_option", setter.strip_option),
+                    ("strip_bool", setter.strip_bool),
+                    ("boxed", setter.boxed),
+                    ("format", setter.format),
+                    ("number_cast", setter.number_cast),
+                    ("try_into", setter.try_into),
+                    ("parse", setter.parse),
+                    ("clamp", clamp),
+                ],
+            )?;
+        }
+
+        if let Some(clamp) = clamp {
+            reject_conflicts(
+                "clamp",
+                clamp,
+                &[
+                    ("strip_bool", setter.strip_bool),
+                    ("boxed", setter.boxed),
+                    ("from_slice", setter.from_slice),
+                    ("format", setter.format),
+                    ("transform", transform),
+                ],
+            )?;
+        }
+
+        if let (Some(format), Some(auto_into)) = (&self.setter.format, &self.setter.auto_into) {
+            if let Some(via) = auto_into.via.first() {
+                let mut error = Error::new(*format, "format conflicts with into(via = ...)");
+                error.combine(Error::new_spanned(via, "via set here"));
+                return Err(error);
+            }
+        }
+
+        if let Some(generic) = setter.auto_into.as_ref().and_then(|auto_into| auto_into.generic.as_ref()) {
+            reject_conflicts(
+                "into(generic = ...)",
+                generic.span(),
+                &[("format", setter.format), ("from_slice", setter.from_slice)],
+            )?;
+        }
+
+        if let (Some(from_slice), Some(auto_into)) = (&self.setter.from_slice, &self.setter.auto_into) {
+            if let Some(via) = auto_into.via.first() {
+                let mut error = Error::new(*from_slice, "from_slice conflicts with into(via = ...)");
+                error.combine(Error::new_spanned(via, "via set here"));
+                return Err(error);
+            }
+        }
+
         let conflicting_transformations = [
             ("transform", self.setter.transform.as_ref().map(|t| &t.span)),
             ("strip_option", self.setter.strip_option.as_ref()),
             ("strip_bool", self.setter.strip_bool.as_ref()),
+            ("boxed", self.setter.boxed.as_ref()),
+            ("from_slice", self.setter.from_slice.as_ref()),
+            ("from_fn", self.setter.from_fn.as_ref()),
+            ("extend", self.setter.extend.as_ref()),
+            ("format", self.setter.format.as_ref()),
         ];
         let mut conflicting_transformations = conflicting_transformations
             .iter()
@@ -211,35 +1072,82 @@ impl<'a> FieldBuilderAttr<'a> {
     }
 }
 
+/// Fails if any of the `conflicting` settings - pairs of a caption and the span where the setting
+/// was set, if it was - is set together with the setting `caption`, which was set at `span`.
+fn reject_conflicts(caption: &str, span: Span, conflicting: &[(&str, Option<Span>)]) -> Result<(), Error> {
+    let Some((conflicting_caption, conflicting_span)) = conflicting
+        .iter()
+        .find_map(|(conflicting_caption, conflicting_span)| Some((conflicting_caption, (*conflicting_span)?)))
+    else {
+        return Ok(());
+    };
+    let mut error = Error::new(span, format_args!("{} conflicts with {}", caption, conflicting_caption));
+    error.combine(Error::new(conflicting_span, format_args!("{} set here", conflicting_caption)));
+    Err(error)
+}
+
 impl ApplyMeta for FieldBuilderAttr<'_> {
     fn apply_meta(&mut self, expr: AttrArg) -> Result<(), Error> {
         match expr.name().to_string().as_str() {
             "default" => match expr {
                 AttrArg::Flag(ident) => {
+                    self.default_once = None;
+                    self.default_try = None;
                     self.default =
                         Some(syn::parse2(quote_spanned!(ident.span() => ::core::default::Default::default())).unwrap());
                     Ok(())
                 }
                 AttrArg::KeyValue(key_value) => {
+                    self.default_once = None;
+                    self.default_try = None;
                     self.default = Some(key_value.parse_value()?);
                     Ok(())
                 }
                 AttrArg::Not { .. } => {
+                    self.default_once = None;
+                    self.default_try = None;
                     self.default = None;
                     Ok(())
                 }
                 AttrArg::Sub(_) => Err(expr.incorrect_type()),
             },
             "default_code" => {
-                use std::str::FromStr;
+                let code = expr.key_value()?.parse_value::<syn::LitStr>()?;
+                self.default_once = None;
+                self.default_try = None;
+                self.default = Some(parse_code_string(&code)?);
 
+                Ok(())
+            }
+            "default_once" => {
+                let key_value = expr.key_value()?;
+                self.default_once = Some(key_value.name.span());
+                self.default_try = None;
+                self.default = Some(key_value.parse_value()?);
+                Ok(())
+            }
+            "default_try" => {
+                let key_value = expr.key_value()?;
+                self.default_once = None;
+                self.default_try = Some(key_value.name.span());
+                self.default = Some(key_value.parse_value()?);
+                Ok(())
+            }
+            "computed" => {
                 let code = expr.key_value()?.parse_value::<syn::LitStr>()?;
-                let tokenized_code = TokenStream::from_str(&code.value())?;
-                self.default = Some(syn::parse2(tokenized_code).map_err(|e| Error::new_spanned(code, format!("{}", e)))?);
+                self.default_once = None;
+                self.default_try = None;
+                self.default = Some(parse_code_string(&code)?);
+                self.computed = Some(code.span());
 
                 Ok(())
             }
+            "missing_message" => {
+                self.missing_message = expr.key_value_or_not()?.map(|kv| kv.parse_value()).transpose()?;
+                Ok(())
+            }
             "setter" => self.setter.apply_sub_attr(expr.sub_attr()?),
+            "secret" => expr.apply_flag_to_field(&mut self.secret, "secret"),
             "mutable_during_default_resolution" => expr.apply_flag_to_field(
                 &mut self.mutable_during_default_resolution,
                 "made mutable during default resolution",
@@ -283,10 +1191,19 @@ impl ApplyMeta for FieldBuilderAttr<'_> {
                 self.mutators.extend(expr.sub_attr()?.undelimited()?);
                 Ok(())
             }
-            _ => Err(Error::new_spanned(
-                expr.name(),
-                format!("Unknown parameter {:?}", expr.name().to_string()),
-            )),
+            _ => Err(expr.unknown_parameter(&[
+                "default",
+                "default_code",
+                "default_once",
+                "default_try",
+                "computed",
+                "missing_message",
+                "setter",
+                "secret",
+                "mutable_during_default_resolution",
+                "via_mutators",
+                "mutators",
+            ])),
         }
     }
 }
@@ -306,6 +1223,14 @@ impl ApplyMeta for SetterSettings {
                 };
                 Ok(())
             }
+            "name" => {
+                self.name = if let Some(key_value) = expr.key_value_or_not()? {
+                    Some(key_value.parse_value()?)
+                } else {
+                    None
+                };
+                Ok(())
+            }
             "prefix" => {
                 self.prefix = if let Some(key_value) = expr.key_value_or_not()? {
                     Some(expr_to_lit_string(&key_value.parse_value()?)?)
@@ -323,17 +1248,278 @@ impl ApplyMeta for SetterSettings {
                 Ok(())
             }
             "skip" => expr.apply_flag_to_field(&mut self.skip, "skipped"),
-            "into" => expr.apply_flag_to_field(&mut self.auto_into, "calling into() on the argument"),
-            "strip_option" => expr.apply_flag_to_field(&mut self.strip_option, "putting the argument in Some(...)"),
+            "lazy" => expr.apply_flag_to_field(&mut self.lazy, "initialized lazily"),
+            "into" => {
+                if let AttrArg::Sub(sub) = expr {
+                    let mut auto_into = AutoInto::empty_spanned(sub.name.span());
+                    auto_into.apply_sub_attr(sub)?;
+                    self.auto_into = Some(auto_into);
+                } else {
+                    let mut span = self.auto_into.as_ref().map(|auto_into| auto_into.span);
+                    expr.apply_flag_to_field(&mut span, "calling into() on the argument")?;
+                    self.auto_into = span.map(AutoInto::empty_spanned);
+                }
+                Ok(())
+            }
+            "each" => {
+                self.each = match expr {
+                    AttrArg::KeyValue(key_value) => {
+                        let mut each = EachSetter::empty_spanned(key_value.name.span());
+                        each.name = Some(key_value.parse_value::<syn::LitStr>()?.parse()?);
+                        Some(each)
+                    }
+                    AttrArg::Sub(sub) => {
+                        let mut each = EachSetter::empty_spanned(sub.name.span());
+                        each.apply_sub_attr(sub)?;
+                        if each.name.is_none() {
+                            return Err(Error::new(each.span, "`each(...)` requires a `name = \"...\"`"));
+                        }
+                        Some(each)
+                    }
+                    AttrArg::Not { .. } => None,
+                    AttrArg::Flag(_) => return Err(expr.incorrect_type()),
+                };
+                Ok(())
+            }
+            "entry" => {
+                self.entry = match expr {
+                    AttrArg::KeyValue(key_value) => {
+                        let mut entry = EntrySetter::empty_spanned(key_value.name.span());
+                        entry.name = Some(key_value.parse_value::<syn::LitStr>()?.parse()?);
+                        Some(entry)
+                    }
+                    AttrArg::Sub(sub) => {
+                        let mut entry = EntrySetter::empty_spanned(sub.name.span());
+                        entry.apply_sub_attr(sub)?;
+                        if entry.name.is_none() {
+                            return Err(Error::new(entry.span, "`entry(...)` requires a `name = \"...\"`"));
+                        }
+                        Some(entry)
+                    }
+                    AttrArg::Not { .. } => None,
+                    AttrArg::Flag(_) => return Err(expr.incorrect_type()),
+                };
+                Ok(())
+            }
+            "strip_option" => match expr {
+                AttrArg::Sub(sub) => {
+                    let span = sub.name.span();
+                    let mut custom = StripOptionCustom {
+                        ty: None,
+                        some: None,
+                        none: None,
+                    };
+                    custom.apply_sub_attr(sub)?;
+                    if custom.ty.is_none() || custom.some.is_none() || custom.none.is_none() {
+                        return Err(Error::new(
+                            span,
+                            "`strip_option(...)` requires `ty = ...`, `some = ...` and `none = ...`",
+                        ));
+                    }
+                    self.strip_option = Some(span);
+                    self.strip_option_custom = Some(custom);
+                    Ok(())
+                }
+                _ => {
+                    self.strip_option_custom = None;
+                    expr.apply_flag_to_field(&mut self.strip_option, "putting the argument in Some(...)")
+                }
+            },
             "strip_bool" => expr.apply_flag_to_field(&mut self.strip_bool, "zero arguments setter, sets the field to true"),
-            _ => Err(Error::new_spanned(
-                expr.name(),
-                format!("Unknown parameter {:?}", expr.name().to_string()),
-            )),
+            "boxed" => expr.apply_flag_to_field(&mut self.boxed, "putting the argument in a Box"),
+            "from_slice" => expr.apply_flag_to_field(&mut self.from_slice, "taking a slice"),
+            "from_fn" => expr.apply_flag_to_field(&mut self.from_fn, "collected from a closure"),
+            "take" => expr.apply_flag_to_field(&mut self.take, "taken from a mutable reference"),
+            "format" => expr.apply_flag_to_field(&mut self.format, "taking format_args!(...)"),
+            "try_into" => {
+                if let AttrArg::Sub(sub) = expr {
+                    self.try_into = Some(sub.name.span());
+                    let mut try_into = TryIntoSettings::default();
+                    try_into.apply_sub_attr(sub)?;
+                    self.try_into_deferred = try_into.deferred;
+                    Ok(())
+                } else {
+                    self.try_into_deferred = None;
+                    expr.apply_flag_to_field(&mut self.try_into, "calling try_into() on the argument")
+                }
+            }
+            "parse" => expr.apply_flag_to_field(&mut self.parse, "parsing the argument"),
+            "convert" => {
+                let key_value = expr.key_value()?;
+                let mode = key_value.parse_value::<Ident>()?;
+                self.auto_into = None;
+                self.try_into = None;
+                self.try_into_deferred = None;
+                self.parse = None;
+                match mode.to_string().as_str() {
+                    "into" => self.auto_into = Some(AutoInto::empty_spanned(mode.span())),
+                    "try_into" => self.try_into = Some(mode.span()),
+                    "parse" => self.parse = Some(mode.span()),
+                    "none" => {}
+                    _ => {
+                        return Err(Error::new_spanned(
+                            mode,
+                            "Unknown conversion - expected one of `into`, `try_into`, `parse` or `none`",
+                        ))
+                    }
+                }
+                Ok(())
+            }
+            "clamp" => {
+                self.clamp = match expr {
+                    AttrArg::Sub(sub) => {
+                        let mut clamp = Clamp {
+                            span: sub.name.span(),
+                            min: None,
+                            max: None,
+                        };
+                        clamp.apply_sub_attr(sub)?;
+                        if clamp.min.is_none() || clamp.max.is_none() {
+                            return Err(Error::new(
+                                clamp.span,
+                                "`clamp(...)` requires both `min = ...` and `max = ...`",
+                            ));
+                        }
+                        Some(clamp)
+                    }
+                    AttrArg::Not { .. } => None,
+                    _ => return Err(expr.incorrect_type()),
+                };
+                Ok(())
+            }
+            "requires_token" => {
+                self.requires_token = if let Some(key_value) = expr.key_value_or_not()? {
+                    Some(key_value.parse_value()?)
+                } else {
+                    None
+                };
+                Ok(())
+            }
+            "number_cast" => expr.apply_flag_to_field(&mut self.number_cast, "casting the argument with `as`"),
+            "merge" => expr.apply_flag_to_field(&mut self.merge, "merging repeated calls"),
+            "indexed" => expr.apply_flag_to_field(&mut self.indexed, "set one index at a time"),
+            "extend" => expr.apply_flag_to_field(&mut self.extend, "extended by repeated calls"),
+            "setter_fn" => expr.apply_flag_to_field(&mut self.setter_fn, "available as a function value"),
+            "field_mut" => expr.apply_flag_to_field(&mut self.field_mut, "accessible via a `_mut` method"),
+            "maybe_into" => expr.apply_flag_to_field(&mut self.maybe_into, "also settable via an `_into` method"),
+            "async_into" => expr.apply_flag_to_field(&mut self.async_into, "awaiting the argument"),
+            "unit" => {
+                let unit = match expr {
+                    AttrArg::KeyValue(key_value) => {
+                        let name = key_value.parse_value::<syn::LitStr>()?;
+                        let Some(arg_type) = known_unit_arg_type(&name.value()) else {
+                            return Err(Error::new_spanned(
+                                &name,
+                                format!(
+                                    "unknown unit `{}` - use `unit(name = \"...\", ty = ..., via = ...)` for custom units",
+                                    name.value()
+                                ),
+                            ));
+                        };
+                        UnitSetter {
+                            name: name.parse()?,
+                            arg_type,
+                            via: None,
+                        }
+                    }
+                    AttrArg::Sub(sub) => {
+                        let span = sub.name.span();
+                        let mut custom = UnitSetterCustom::default();
+                        custom.apply_sub_attr(sub)?;
+                        let (Some(name), Some(arg_type), Some(via)) = (custom.name, custom.ty, custom.via) else {
+                            return Err(Error::new(
+                                span,
+                                "`unit(...)` requires `name = \"...\"`, `ty = ...` and `via = ...`",
+                            ));
+                        };
+                        UnitSetter {
+                            name,
+                            arg_type,
+                            via: Some(via),
+                        }
+                    }
+                    AttrArg::Not { .. } => {
+                        self.units.clear();
+                        return Ok(());
+                    }
+                    AttrArg::Flag(_) => return Err(expr.incorrect_type()),
+                };
+                if self.units.iter().any(|existing| existing.name == unit.name) {
+                    return Err(Error::new_spanned(
+                        &unit.name,
+                        format!("unit `{}` is specified more than once", unit.name),
+                    ));
+                }
+                self.units.push(unit);
+                Ok(())
+            }
+            _ => Err(expr.unknown_parameter(&[
+                "doc",
+                "transform",
+                "name",
+                "prefix",
+                "suffix",
+                "skip",
+                "lazy",
+                "into",
+                "strip_option",
+                "strip_bool",
+                "boxed",
+                "from_slice",
+                "from_fn",
+                "take",
+                "number_cast",
+                "try_into",
+                "parse",
+                "convert",
+                "each",
+                "entry",
+                "indexed",
+                "format",
+                "setter_fn",
+                "field_mut",
+                "maybe_into",
+                "async_into",
+                "unit",
+                "clamp",
+                "requires_token",
+                "merge",
+                "extend",
+            ])),
         }
     }
 }
 
+fn is_string(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "String"),
+        _ => false,
+    }
+}
+
+fn is_primitive_number(ty: &syn::Type) -> bool {
+    const PRIMITIVE_NUMBERS: &[&str] = &[
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize", "f32", "f64",
+    ];
+    match ty {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => {
+            path_to_single_string(&type_path.path).is_some_and(|name| PRIMITIVE_NUMBERS.contains(&name.as_str()))
+        }
+        _ => false,
+    }
+}
+
+fn parse_code_string(code: &syn::LitStr) -> Result<syn::Expr, Error> {
+    use std::str::FromStr;
+
+    let tokenized_code = TokenStream::from_str(&code.value())?;
+    syn::parse2(tokenized_code).map_err(|e| Error::new_spanned(code, format!("{}", e)))
+}
+
 #[derive(Debug, Clone)]
 pub struct Transform {
     pub params: Vec<(syn::Pat, syn::Type)>,
@@ -369,6 +1555,239 @@ fn parse_transform_closure(span: Span, expr: syn::Expr) -> Result<Transform, Err
     })
 }
 
+#[derive(Debug, Clone)]
+pub struct AutoInto {
+    pub span: Span,
+    /// Types the argument is converted through, in order, before converting it to the field type.
+    pub via: Vec<syn::Type>,
+    /// Name of a generic parameter of the setter to use for the argument type instead of `impl Into<...>`.
+    pub generic: Option<Ident>,
+}
+
+impl AutoInto {
+    pub fn empty_spanned(span: Span) -> Self {
+        Self {
+            span,
+            via: Vec::new(),
+            generic: None,
+        }
+    }
+}
+
+impl ApplyMeta for AutoInto {
+    fn apply_meta(&mut self, expr: AttrArg) -> Result<(), Error> {
+        match expr.name().to_string().as_str() {
+            "via" => {
+                let parse_types = |input: syn::parse::ParseStream| {
+                    if input.peek(syn::token::Bracket) {
+                        let content;
+                        syn::bracketed!(content in input);
+                        Ok(Punctuated::<syn::Type, Token![,]>::parse_terminated(&content)?
+                            .into_iter()
+                            .collect())
+                    } else {
+                        Ok(vec![input.parse()?])
+                    }
+                };
+                self.via = parse_types.parse2(expr.key_value()?.value)?;
+                Ok(())
+            }
+            "generic" => {
+                self.generic = Some(expr.key_value()?.parse_value::<syn::LitStr>()?.parse()?);
+                Ok(())
+            }
+            _ => Err(expr.unknown_parameter(&["via", "generic"])),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EachSetter {
+    pub span: Span,
+    pub name: Option<Ident>,
+    /// Whether to convert each argument with `Into`.
+    pub into: Option<Span>,
+    /// The maximal number of items the field can have.
+    pub max: Option<syn::LitInt>,
+}
+
+impl EachSetter {
+    fn empty_spanned(span: Span) -> Self {
+        Self {
+            span,
+            name: None,
+            into: None,
+            max: None,
+        }
+    }
+}
+
+impl ApplyMeta for EachSetter {
+    fn apply_meta(&mut self, expr: AttrArg) -> Result<(), Error> {
+        match expr.name().to_string().as_str() {
+            "name" => {
+                let name = expr.key_value()?.parse_value::<syn::LitStr>()?;
+                self.name = Some(name.parse()?);
+                Ok(())
+            }
+            "into" => expr.apply_flag_to_field(&mut self.into, "calling into() on the arguments"),
+            "max" => {
+                let max = expr.key_value()?.parse_value::<syn::LitInt>()?;
+                if max.base10_parse::<usize>()? == 0 {
+                    return Err(Error::new_spanned(max, "`max` must be at least 1"));
+                }
+                self.max = Some(max);
+                Ok(())
+            }
+            _ => Err(expr.unknown_parameter(&["name", "into", "max"])),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct TryIntoSettings {
+    deferred: Option<Span>,
+}
+
+impl ApplyMeta for TryIntoSettings {
+    fn apply_meta(&mut self, expr: AttrArg) -> Result<(), Error> {
+        match expr.name().to_string().as_str() {
+            "deferred" => expr.apply_flag_to_field(&mut self.deferred, "converting the argument when building"),
+            _ => Err(expr.unknown_parameter(&["deferred"])),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EntrySetter {
+    pub span: Span,
+    pub name: Option<Ident>,
+    /// Whether to convert the key and the value with `Into`.
+    pub into: Option<Span>,
+}
+
+impl EntrySetter {
+    fn empty_spanned(span: Span) -> Self {
+        Self {
+            span,
+            name: None,
+            into: None,
+        }
+    }
+}
+
+impl ApplyMeta for EntrySetter {
+    fn apply_meta(&mut self, expr: AttrArg) -> Result<(), Error> {
+        match expr.name().to_string().as_str() {
+            "name" => {
+                let name = expr.key_value()?.parse_value::<syn::LitStr>()?;
+                self.name = Some(name.parse()?);
+                Ok(())
+            }
+            "into" => expr.apply_flag_to_field(&mut self.into, "calling into() on the key and the value"),
+            _ => Err(expr.unknown_parameter(&["name", "into"])),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Clamp {
+    pub span: Span,
+    pub min: Option<syn::Expr>,
+    pub max: Option<syn::Expr>,
+}
+
+impl ApplyMeta for Clamp {
+    fn apply_meta(&mut self, expr: AttrArg) -> Result<(), Error> {
+        match expr.name().to_string().as_str() {
+            "min" => {
+                self.min = Some(expr.key_value()?.parse_value()?);
+                Ok(())
+            }
+            "max" => {
+                self.max = Some(expr.key_value()?.parse_value()?);
+                Ok(())
+            }
+            _ => Err(expr.unknown_parameter(&["min", "max"])),
+        }
+    }
+}
+
+/// A custom option-like type for `strip_option(ty = ..., some = ..., none = ...)`.
+#[derive(Debug, Clone)]
+pub struct StripOptionCustom {
+    pub ty: Option<syn::Path>,
+    pub some: Option<syn::ExprPath>,
+    pub none: Option<syn::Expr>,
+}
+
+impl ApplyMeta for StripOptionCustom {
+    fn apply_meta(&mut self, expr: AttrArg) -> Result<(), Error> {
+        match expr.name().to_string().as_str() {
+            "ty" => {
+                self.ty = Some(expr.key_value()?.parse_value()?);
+                Ok(())
+            }
+            "some" => {
+                self.some = Some(expr.key_value()?.parse_value()?);
+                Ok(())
+            }
+            "none" => {
+                self.none = Some(expr.key_value()?.parse_value()?);
+                Ok(())
+            }
+            _ => Err(expr.unknown_parameter(&["ty", "some", "none"])),
+        }
+    }
+}
+
+/// An additional setter, from `unit = "..."` or `unit(name = "...", ty = ..., via = ...)`, that
+/// takes a quantity in that unit.
+#[derive(Debug, Clone)]
+pub struct UnitSetter {
+    pub name: Ident,
+    pub arg_type: syn::Type,
+    /// The constructor to pass the quantity to - `<T>::from_<name>` when not given.
+    pub via: Option<syn::ExprPath>,
+}
+
+/// The units of `unit = "..."`, named like the constructors of `core::time::Duration`.
+fn known_unit_arg_type(name: &str) -> Option<syn::Type> {
+    Some(match name {
+        "secs" | "millis" | "micros" | "nanos" => syn::parse_quote!(u64),
+        "secs_f32" => syn::parse_quote!(f32),
+        "secs_f64" => syn::parse_quote!(f64),
+        _ => return None,
+    })
+}
+
+#[derive(Default)]
+struct UnitSetterCustom {
+    name: Option<Ident>,
+    ty: Option<syn::Type>,
+    via: Option<syn::ExprPath>,
+}
+
+impl ApplyMeta for UnitSetterCustom {
+    fn apply_meta(&mut self, expr: AttrArg) -> Result<(), Error> {
+        match expr.name().to_string().as_str() {
+            "name" => {
+                self.name = Some(expr.key_value()?.parse_value::<syn::LitStr>()?.parse()?);
+                Ok(())
+            }
+            "ty" => {
+                self.ty = Some(expr.key_value()?.parse_value()?);
+                Ok(())
+            }
+            "via" => {
+                self.via = Some(expr.key_value()?.parse_value()?);
+                Ok(())
+            }
+            _ => Err(expr.unknown_parameter(&["name", "ty", "via"])),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ViaMutators {
     pub span: Span,
@@ -391,10 +1810,7 @@ impl ApplyMeta for ViaMutators {
                 self.init = expr.key_value()?.parse_value()?;
                 Ok(())
             }
-            _ => Err(Error::new_spanned(
-                expr.name(),
-                format!("Unknown parameter {:?}", expr.name().to_string()),
-            )),
+            _ => Err(expr.unknown_parameter(&["init"])),
         }
     }
 }
@@ -1,10 +1,12 @@
 use proc_macro2::TokenStream;
+use quote::ToTokens;
 use syn::{parse::Error, parse_macro_input, spanned::Spanned, DeriveInput};
 
 mod builder_attr;
 mod field_info;
 mod mutator;
 mod struct_info;
+mod union_info;
 mod util;
 
 #[proc_macro_derive(TypedBuilder, attributes(builder))]
@@ -16,15 +18,370 @@ pub fn derive_typed_builder(input: proc_macro::TokenStream) -> proc_macro::Token
     }
 }
 
+#[proc_macro_attribute]
+pub fn builder(args: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let item = parse_macro_input!(input as syn::Item);
+    let result = match item {
+        syn::Item::Fn(function) => impl_function_builder(args.into(), function),
+        syn::Item::Impl(item_impl) => impl_constructor_builders(args.into(), item_impl),
+        item => Err(Error::new_spanned(
+            item,
+            "`#[builder]` can only be used on functions and on `impl` blocks",
+        )),
+    };
+    match result {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+#[proc_macro_attribute]
+pub fn typed_builder_alias(args: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let alias = parse_macro_input!(args as builder_attr::BuilderAlias);
+    let ast = parse_macro_input!(input as DeriveInput);
+    match impl_builder_alias(&alias, &ast) {
+        Ok(output) => output.into(),
+        Err(error) => {
+            let mut output = ast.to_token_stream();
+            output.extend(error.to_compile_error());
+            output.into()
+        }
+    }
+}
+
 fn impl_my_derive(ast: &syn::DeriveInput) -> Result<TokenStream, Error> {
     let data = match &ast.data {
         syn::Data::Struct(data) => match &data.fields {
             syn::Fields::Named(fields) => struct_info::StructInfo::new(ast, fields.named.iter())?.derive()?,
-            syn::Fields::Unnamed(_) => return Err(Error::new(ast.span(), "TypedBuilder is not supported for tuple structs")),
+            syn::Fields::Unnamed(fields) => {
+                let fields = positional_fields(fields);
+                struct_info::StructInfo::new(ast, fields.iter())?.derive()?
+            }
             syn::Fields::Unit => return Err(Error::new(ast.span(), "TypedBuilder is not supported for unit structs")),
         },
-        syn::Data::Enum(_) => return Err(Error::new(ast.span(), "TypedBuilder is not supported for enums")),
-        syn::Data::Union(_) => return Err(Error::new(ast.span(), "TypedBuilder is not supported for unions")),
+        syn::Data::Enum(data) => {
+            if !data
+                .variants
+                .iter()
+                .any(|variant| matches!(variant.fields, syn::Fields::Named(_)))
+            {
+                return Err(Error::new(
+                    ast.ident.span(),
+                    "TypedBuilder requires at least one enum variant with named fields - tuple and unit variants get no builder",
+                ));
+            }
+            data.variants
+                .iter()
+                .filter_map(|variant| match &variant.fields {
+                    syn::Fields::Named(fields) => Some(struct_info::StructInfo::new_for_variant(ast, variant, fields)),
+                    // Tuple and unit variants get no builder.
+                    _ => None,
+                })
+                .map(|struct_info| struct_info?.derive())
+                .collect::<Result<_, _>>()?
+        }
+        syn::Data::Union(data) => union_info::UnionInfo::new(ast, data.fields.named.iter())?.derive()?,
     };
     Ok(data)
 }
+
+/// The builder refers to the positional fields of tuple structs as `_0`, `_1`, ...
+fn positional_fields(fields: &syn::FieldsUnnamed) -> Vec<syn::Field> {
+    fields
+        .unnamed
+        .iter()
+        .enumerate()
+        .map(|(i, field)| syn::Field {
+            ident: Some(quote::format_ident!("_{}", i)),
+            ..field.clone()
+        })
+        .collect()
+}
+
+/// Keeps the struct as it is, and declares the alias of an instance of it and of its builder type.
+fn impl_builder_alias(alias: &builder_attr::BuilderAlias, ast: &syn::DeriveInput) -> Result<TokenStream, Error> {
+    let derives_typed_builder = ast.attrs.iter().any(|attr| {
+        attr.path().is_ident("derive")
+            && attr
+                .parse_args_with(syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated)
+                .is_ok_and(|paths| {
+                    paths
+                        .iter()
+                        .any(|path| path.segments.last().is_some_and(|segment| segment.ident == "TypedBuilder"))
+                })
+    });
+    if !derives_typed_builder {
+        return Err(Error::new_spanned(
+            &alias.ident,
+            "`typed_builder_alias` must be placed above `#[derive(TypedBuilder)]`",
+        ));
+    }
+    let positional;
+    let struct_info = match &ast.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => struct_info::StructInfo::new(ast, fields.named.iter()),
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Unnamed(fields),
+            ..
+        }) => {
+            positional = positional_fields(fields);
+            struct_info::StructInfo::new(ast, positional.iter())
+        }
+        _ => {
+            return Err(Error::new_spanned(
+                &alias.ident,
+                "`typed_builder_alias` can only be used on structs with fields",
+            ))
+        }
+    };
+    let mut output = ast.to_token_stream();
+    // The derive reports the errors in the struct's settings.
+    if let Ok(struct_info) = struct_info {
+        output.extend(struct_info.builder_alias_impl(alias)?);
+    }
+    Ok(output)
+}
+
+/// Describes the parameters of the function as the fields of a struct, and builds that.
+fn impl_function_builder(args: TokenStream, mut function: syn::ItemFn) -> Result<TokenStream, Error> {
+    let fields = parameters_to_fields(&mut function.sig)?;
+    let ast = parameters_struct(
+        args_to_attrs(args),
+        function.vis.clone(),
+        quote::format_ident!(
+            "{}",
+            util::to_pascal_case(&util::strip_raw_ident_prefix(function.sig.ident.to_string()))
+        ),
+        function.sig.generics.clone(),
+        fields,
+    );
+    derive_for_cfg_combinations(ast, |ast| {
+        struct_info::StructInfo::new_for_function(ast, &function, None, named_fields(ast))?.derive()
+    })
+}
+
+/// Generates a builder for each associated function in the `impl` block that is marked with
+/// `#[builder]` or `#[builder(...)]`, whose build method calls that function.
+fn impl_constructor_builders(args: TokenStream, mut item_impl: syn::ItemImpl) -> Result<TokenStream, Error> {
+    if let Some((_, path, _)) = &item_impl.trait_ {
+        return Err(Error::new_spanned(path, "`#[builder]` is not supported for trait impls"));
+    }
+    let self_type_name = match &*item_impl.self_ty {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => {
+            type_path.path.segments.last().expect("paths are not empty").ident.clone()
+        }
+        self_ty => return Err(Error::new_spanned(self_ty, "expected a type name")),
+    };
+
+    let mut builders = Vec::new();
+    for impl_item in item_impl.items.iter_mut() {
+        let syn::ImplItem::Fn(method) = impl_item else {
+            continue;
+        };
+        let (builder_attrs, attrs): (Vec<_>, _) = method.attrs.drain(..).partition(|attr| attr.path().is_ident("builder"));
+        method.attrs = attrs;
+        if builder_attrs.is_empty() {
+            continue;
+        }
+        if let Some(param) = method.sig.generics.params.first() {
+            return Err(Error::new_spanned(param, "generic constructors are not supported"));
+        }
+        let fields = parameters_to_fields(&mut method.sig)?;
+        builders.push((method.clone(), builder_attrs, fields));
+    }
+    if builders.is_empty() {
+        return Err(Error::new_spanned(
+            &item_impl.self_ty,
+            "no associated function in this `impl` block is marked with `#[builder]`",
+        ));
+    }
+
+    let self_ty = &*item_impl.self_ty;
+    let mut output = item_impl.to_token_stream();
+    for (method, builder_attrs, fields) in builders {
+        // In the builder, `Self` would be the builder type.
+        let fields = fields
+            .into_iter()
+            .map(|mut field| {
+                field.ty = syn::parse2(util::replace_self_type(field.ty.to_token_stream(), self_ty))?;
+                Ok(field)
+            })
+            .collect::<Result<_, Error>>()?;
+        let mut sig = method.sig;
+        sig.output = syn::parse2(util::replace_self_type(sig.output.to_token_stream(), self_ty))?;
+        let function = syn::ItemFn {
+            attrs: Vec::new(),
+            vis: method.vis.clone(),
+            sig,
+            block: Box::new(method.block),
+        };
+        let name = if function.sig.ident == "new" {
+            self_type_name.clone()
+        } else {
+            quote::format_ident!(
+                "{}{}",
+                self_type_name,
+                util::to_pascal_case(&util::strip_raw_ident_prefix(function.sig.ident.to_string()))
+            )
+        };
+        let mut attrs = args_to_attrs(args.clone());
+        attrs.extend(builder_attrs);
+        let ast = parameters_struct(attrs, method.vis, name, item_impl.generics.clone(), fields);
+        output.extend(derive_for_cfg_combinations(ast, |ast| {
+            struct_info::StructInfo::new_for_function(ast, &function, Some(self_ty), named_fields(ast))?.derive()
+        })?);
+    }
+    Ok(output)
+}
+
+/// The arguments of the attribute macro, as the `#[builder(...)]` attribute of a struct.
+fn args_to_attrs(args: TokenStream) -> Vec<syn::Attribute> {
+    if args.is_empty() {
+        Vec::new()
+    } else {
+        vec![syn::parse_quote!(#[builder(#args)])]
+    }
+}
+
+/// Turns the parameters into fields, moving their `#[builder(...)]` attributes to the fields and
+/// copying their `#[cfg(...)]` attributes for `derive_for_cfg_combinations`.
+fn parameters_to_fields(sig: &mut syn::Signature) -> Result<syn::punctuated::Punctuated<syn::Field, syn::Token![,]>, Error> {
+    if let Some(asyncness) = sig.asyncness {
+        return Err(Error::new_spanned(
+            asyncness,
+            "`#[builder]` is not supported for async functions",
+        ));
+    }
+    let mut fields = syn::punctuated::Punctuated::new();
+    for input in sig.inputs.iter_mut() {
+        let syn::FnArg::Typed(pat_type) = input else {
+            return Err(Error::new_spanned(input, "`#[builder]` is not supported for methods"));
+        };
+        let syn::Pat::Ident(syn::PatIdent { ident, subpat: None, .. }) = &*pat_type.pat else {
+            return Err(Error::new_spanned(&pat_type.pat, "expected a parameter name"));
+        };
+        if let syn::Type::ImplTrait(impl_trait) = &*pat_type.ty {
+            return Err(Error::new_spanned(
+                impl_trait,
+                "`impl Trait` parameters are not supported - use a generic parameter instead",
+            ));
+        }
+        let (mut field_attrs, attrs): (Vec<_>, _) = pat_type.attrs.drain(..).partition(|attr| attr.path().is_ident("builder"));
+        pat_type.attrs = attrs;
+        field_attrs.extend(pat_type.attrs.iter().filter(|attr| attr.path().is_ident("cfg")).cloned());
+        fields.push(syn::Field {
+            attrs: field_attrs,
+            vis: syn::Visibility::Inherited,
+            mutability: syn::FieldMutability::None,
+            ident: Some(ident.clone()),
+            colon_token: Some(Default::default()),
+            ty: (*pat_type.ty).clone(),
+        });
+    }
+    Ok(fields)
+}
+
+/// The most distinct `#[cfg(...)]` predicates the parameters of a function can have - the builder is
+/// derived once for each combination of them.
+const MAX_PARAMETER_CFG_PREDICATES: usize = 4;
+
+/// Unlike the fields of a derive input, the parameters of a function are not stripped of their
+/// `#[cfg(...)]` attributes before the macro sees them, and the macro cannot evaluate the
+/// predicates itself. So the builder is derived once for every combination of the predicates, with
+/// the parameters that combination enables, and each derivation is gated behind its combination.
+fn derive_for_cfg_combinations(
+    ast: syn::DeriveInput,
+    mut derive: impl FnMut(&syn::DeriveInput) -> Result<TokenStream, Error>,
+) -> Result<TokenStream, Error> {
+    let mut predicates = Vec::<syn::Meta>::new();
+    let mut field_predicates = Vec::new();
+    for field in named_fields(&ast) {
+        let mut indices = Vec::new();
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("cfg")) {
+            let predicate: syn::Meta = attr.parse_args()?;
+            let index = predicates.iter().position(|p| *p == predicate).unwrap_or_else(|| {
+                predicates.push(predicate);
+                predicates.len() - 1
+            });
+            indices.push(index);
+        }
+        field_predicates.push(indices);
+    }
+    if predicates.is_empty() {
+        return derive(&ast);
+    }
+    if let Some(predicate) = predicates.get(MAX_PARAMETER_CFG_PREDICATES) {
+        return Err(Error::new_spanned(
+            predicate,
+            format!("`#[builder]` supports at most {MAX_PARAMETER_CFG_PREDICATES} distinct `cfg` predicates on parameters"),
+        ));
+    }
+
+    let mut output = TokenStream::new();
+    for combination in 0..1_usize << predicates.len() {
+        let enabled = |index: usize| combination & (1 << index) != 0;
+        let mut ast = ast.clone();
+        if let syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) = &mut ast.data
+        {
+            fields.named = std::mem::take(&mut fields.named)
+                .into_iter()
+                .zip(&field_predicates)
+                .filter(|(_, indices)| indices.iter().all(|&index| enabled(index)))
+                .map(|(mut field, _)| {
+                    field.attrs.retain(|attr| !attr.path().is_ident("cfg"));
+                    field
+                })
+                .collect();
+        }
+        let conditions = predicates.iter().enumerate().map(|(index, predicate)| {
+            if enabled(index) {
+                quote::quote!(#predicate)
+            } else {
+                quote::quote!(not(#predicate))
+            }
+        });
+        let condition: syn::Meta = syn::parse_quote!(all(#( #conditions ),*));
+        output.extend(util::gate_items_behind_cfg(derive(&ast)?, &condition)?);
+    }
+    Ok(output)
+}
+
+/// A struct with the parameters of a function as its fields, for `StructInfo`.
+fn parameters_struct(
+    attrs: Vec<syn::Attribute>,
+    vis: syn::Visibility,
+    ident: syn::Ident,
+    generics: syn::Generics,
+    fields: syn::punctuated::Punctuated<syn::Field, syn::Token![,]>,
+) -> syn::DeriveInput {
+    syn::DeriveInput {
+        attrs,
+        vis,
+        ident,
+        generics,
+        data: syn::Data::Struct(syn::DataStruct {
+            struct_token: Default::default(),
+            fields: syn::Fields::Named(syn::FieldsNamed {
+                brace_token: Default::default(),
+                named: fields,
+            }),
+            semi_token: None,
+        }),
+    }
+}
+
+fn named_fields(ast: &syn::DeriveInput) -> impl Iterator<Item = &syn::Field> {
+    let syn::Data::Struct(syn::DataStruct {
+        fields: syn::Fields::Named(fields),
+        ..
+    }) = &ast.data
+    else {
+        unreachable!("constructed by parameters_struct")
+    };
+    fields.named.iter()
+}
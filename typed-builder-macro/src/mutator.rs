@@ -1,53 +1,39 @@
 use std::collections::HashSet;
 
-use proc_macro2::Ident;
+use proc_macro2::{Ident, Span};
 use syn::{
     parse::{Parse, ParseStream},
     parse_quote,
     punctuated::Punctuated,
     spanned::Spanned,
-    Error, Expr, FnArg, ItemFn, PatIdent, ReturnType, Signature, Token, Type,
+    Error, FnArg, ItemFn, PatIdent, ReturnType, Signature, Token, Type,
 };
 
-use crate::util::{pat_to_ident, ApplyMeta, AttrArg};
+use crate::util::{expr_to_field_names, pat_to_ident, ApplyMeta, AttrArg};
 
 #[derive(Debug, Clone)]
 pub struct Mutator {
     pub fun: ItemFn,
     pub required_fields: HashSet<Ident>,
+    pub auto: Option<Span>,
 }
 
 #[derive(Default)]
 struct MutatorAttribute {
     requires: HashSet<Ident>,
+    auto: Option<Span>,
 }
 
 impl ApplyMeta for MutatorAttribute {
     fn apply_meta(&mut self, expr: AttrArg) -> Result<(), Error> {
-        if expr.name() != "requires" {
-            return Err(Error::new_spanned(expr.name(), "Only `requires` is supported"));
-        }
-
-        match expr.key_value()?.parse_value()? {
-            Expr::Array(syn::ExprArray { elems, .. }) => self.requires.extend(
-                elems
-                    .into_iter()
-                    .map(|expr| match expr {
-                        Expr::Path(path) if path.path.get_ident().is_some() => {
-                            Ok(path.path.get_ident().cloned().expect("should be ident"))
-                        }
-                        expr => Err(Error::new_spanned(expr, "Expected field name")),
-                    })
-                    .collect::<Result<Vec<_>, _>>()?,
-            ),
-            expr => {
-                return Err(Error::new_spanned(
-                    expr,
-                    "Only list of field names [field1, field2, …] supported",
-                ))
+        match expr.name().to_string().as_str() {
+            "requires" => {
+                self.requires.extend(expr_to_field_names(expr.key_value()?.parse_value()?)?);
+                Ok(())
             }
+            "auto" => expr.apply_flag_to_field(&mut self.auto, "running automatically"),
+            _ => Err(expr.unknown_parameter(&["requires", "auto"])),
         }
-        Ok(())
     }
 }
 
@@ -83,9 +69,18 @@ impl Parse for Mutator {
             ));
         };
 
+        if let Some(auto) = attribute.auto {
+            if let Some(arg) = fun.sig.inputs.iter().nth(1) {
+                let mut error = Error::new_spanned(arg, "automatic mutators cannot take arguments");
+                error.combine(Error::new(auto, "auto set here"));
+                return Err(error);
+            }
+        }
+
         Ok(Self {
             fun,
             required_fields: attribute.requires,
+            auto: attribute.auto,
         })
     }
 }
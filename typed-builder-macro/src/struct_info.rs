@@ -1,19 +1,30 @@
+use std::collections::HashSet;
+
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{format_ident, quote, quote_spanned, ToTokens};
-use syn::{parse::Error, parse_quote, punctuated::Punctuated, GenericArgument, ItemFn, Token};
+use syn::{parse_quote, punctuated::Punctuated, spanned::Spanned, GenericArgument, ItemFn, Token};
 
-use crate::builder_attr::{IntoSetting, TypeBuilderAttr};
+use crate::builder_attr::{BuilderAlias, IntoSetting, TypeBuilderAttr};
 use crate::field_info::FieldInfo;
 use crate::mutator::Mutator;
 use crate::util::{
-    empty_type, empty_type_tuple, first_visibility, modify_types_generics_hack, public_visibility, strip_raw_ident_prefix,
-    type_tuple,
+    empty_type, empty_type_tuple, find_variable_use, first_visibility, gate_items_behind_cfg, modify_types_generics_hack,
+    public_visibility, replace_generic_params, strip_raw_ident_prefix, to_snake_case, tokens_to_readable_string, type_tuple,
+    uses_associated_type_shorthand,
 };
 
 #[derive(Debug)]
 pub struct StructInfo<'a> {
     vis: &'a syn::Visibility,
     name: &'a syn::Ident,
+    /// The enum variant the builder builds, when deriving for an enum.
+    variant: Option<&'a syn::Ident>,
+    /// Whether the fields are positional, named `_0`, `_1`, ... for the builder.
+    tuple_struct: bool,
+    /// The function the build method calls with the fields, for `#[builder]` on functions.
+    function: Option<&'a syn::ItemFn>,
+    /// The type the function is an associated function of, for `#[builder]` on constructors.
+    self_type: Option<&'a syn::Type>,
     generics: &'a syn::Generics,
     fields: Vec<FieldInfo<'a>>,
 
@@ -47,21 +58,429 @@ impl<'a> StructInfo<'a> {
             .collect()
     }
 
+    /// A `PhantomData` that uses all the lifetime and type parameters of the struct.
+    fn phantom_type(&self) -> TokenStream {
+        let phantom_generics = self.generics.params.iter().filter_map(|param| match param {
+            syn::GenericParam::Lifetime(lifetime) => {
+                let lifetime = &lifetime.lifetime;
+                Some(quote!(&#lifetime ()))
+            }
+            syn::GenericParam::Type(ty) => {
+                let ty = &ty.ident;
+                Some(ty.to_token_stream())
+            }
+            syn::GenericParam::Const(_cnst) => None,
+        });
+        quote!(::core::marker::PhantomData<(#( ::core::marker::PhantomData<#phantom_generics> ),*)>)
+    }
+
+    /// The type of the fields of a freshly created builder.
+    fn init_fields_type(&self) -> syn::TypeTuple {
+        type_tuple(self.included_fields().map(|f| {
+            if f.builder_attr.via_mutators.is_some() || self.is_seeded_by_builder_method(f) {
+                f.tuplized_type_ty_param()
+            } else {
+                empty_type()
+            }
+        }))
+    }
+
+    /// The default for the fields parameter of the builder type. There is none when the fields of
+    /// a fresh builder name associated types by their shorthand, which cannot be resolved there.
+    fn init_fields_type_default(&self) -> Option<syn::TypeTuple> {
+        let init_fields_type = self.init_fields_type();
+        (!uses_associated_type_shorthand(init_fields_type.to_token_stream(), self.generics)).then_some(init_fields_type)
+    }
+
+    /// The error type of the build method when fields have deferred `try_into` setters or
+    /// `default_try` defaults.
+    fn deferred_error_type(&self) -> Option<&syn::Type> {
+        if let Some(deferred_error) = self.fields.iter().find_map(|field| field.deferred_error.as_ref()) {
+            return Some(deferred_error);
+        }
+        if self.fields.iter().all(|field| field.builder_attr.default_try.is_none()) {
+            return None;
+        }
+        let build_method = &self.builder_attr.build_method;
+        build_method
+            .error
+            .as_ref()
+            .or(build_method.post.as_ref().map(|post| &post.error_type))
+    }
+
+    /// Whether the field is set by a parameter of the builder method.
+    fn is_seeded_by_builder_method(&self, field: &FieldInfo) -> bool {
+        self.builder_attr
+            .builder_method
+            .params
+            .iter()
+            .any(|param| matches!(&*param.pat, syn::Pat::Ident(pat_ident) if pat_ident.ident == *field.name))
+    }
+
+    /// Moves the context, if there is one, from `self` into a reconstructed builder.
+    fn context_passthrough(&self) -> TokenStream {
+        if self.builder_attr.context.is_some() {
+            quote!(context: self.context,)
+        } else {
+            quote!()
+        }
+    }
+
+    /// Binds a reference to the context, if there is one, for the default expressions.
+    fn context_binding(&self) -> TokenStream {
+        if self.builder_attr.context.is_some() {
+            quote! {
+                #[allow(unused_variables)]
+                let context = &self.context;
+            }
+        } else {
+            quote!()
+        }
+    }
+
+    /// How the field is accessed on the built value - `0` rather than `_0` for tuple structs.
+    fn member(&self, field: &FieldInfo) -> syn::Member {
+        if self.tuple_struct {
+            syn::Member::Unnamed(syn::Index::from(field.ordinal))
+        } else {
+            syn::Member::Named(field.name.clone())
+        }
+    }
+
+    /// The name of the built type for the docs - `Enum::Variant` for enum variants.
+    fn display_name(&self) -> String {
+        if let Some(remote) = &self.builder_attr.remote {
+            tokens_to_readable_string(&remote.to_token_stream())
+        } else if let (Some(function), Some(self_type)) = (self.function, self.self_type) {
+            format!(
+                "{}::{}",
+                tokens_to_readable_string(&self_type.to_token_stream()),
+                function.sig.ident
+            )
+        } else if let Some(function) = self.function {
+            function.sig.ident.to_string()
+        } else if let Some(variant) = self.variant {
+            format!("{}::{}", self.name, variant)
+        } else {
+            self.name.to_string()
+        }
+    }
+
+    /// The default name of a generated type, e.g. `FooBuilder` - or `EnumVariantBuilder` for enum variants.
+    fn default_type_name(name: &syn::Ident, variant: Option<&syn::Ident>, suffix: &str) -> String {
+        let variant = variant.map(|variant| strip_raw_ident_prefix(variant.to_string()));
+        strip_raw_ident_prefix(format!("{}{}{}", name, variant.unwrap_or_default(), suffix))
+    }
+
     pub fn new(ast: &'a syn::DeriveInput, fields: impl Iterator<Item = &'a syn::Field>) -> syn::Result<StructInfo<'a>> {
         let builder_attr = TypeBuilderAttr::new(&ast.attrs)?;
+        if let Some(remote) = &builder_attr.remote {
+            Self::check_settings_of_types(&builder_attr, remote.span(), "remote types")?;
+        }
+        Self::with_builder_attr(ast, None, builder_attr, fields)
+    }
+
+    /// A builder for a struct-like variant of an enum, with the `#[builder(...)]` attributes of the
+    /// enum applied before those of the variant.
+    pub fn new_for_variant(
+        ast: &'a syn::DeriveInput,
+        variant: &'a syn::Variant,
+        fields: &'a syn::FieldsNamed,
+    ) -> syn::Result<StructInfo<'a>> {
+        let attrs = ast.attrs.iter().chain(variant.attrs.iter()).cloned().collect::<Vec<_>>();
+        let builder_attr = TypeBuilderAttr::new(&attrs)?;
+        if let Some(remote) = &builder_attr.remote {
+            return Err(syn::Error::new_spanned(remote, "`remote` is not supported for enums"));
+        }
+        Self::check_settings_of_types(&builder_attr, variant.ident.span(), "enums")?;
+        Self::with_builder_attr(ast, Some(&variant.ident), builder_attr, fields.named.iter())
+    }
+
+    /// A builder for the parameters of a function, whose build method calls it. `ast` describes the
+    /// parameters as the fields of a struct, with the function's generics - or those of the `impl`
+    /// block, for associated functions of `self_type` - and the `#[builder(...)]` arguments of the
+    /// attribute macro.
+    pub fn new_for_function(
+        ast: &'a syn::DeriveInput,
+        function: &'a syn::ItemFn,
+        self_type: Option<&'a syn::Type>,
+        fields: impl Iterator<Item = &'a syn::Field>,
+    ) -> syn::Result<StructInfo<'a>> {
+        let builder_attr = TypeBuilderAttr::new(&ast.attrs)?;
+        let span = function.sig.ident.span();
+        Self::check_settings_of_types(&builder_attr, span, "functions")?;
+        let unsupported = [
+            ("remote", builder_attr.remote.as_ref().map(|remote| remote.span())),
+            ("build_via", builder_attr.build_via.as_ref().map(|_| span)),
+            (
+                "short_circuit",
+                builder_attr.short_circuit.as_ref().map(|short_circuit| short_circuit.span),
+            ),
+        ];
+        if let Some((caption, span)) = unsupported.iter().find_map(|(caption, span)| Some((caption, (*span)?))) {
+            return Err(syn::Error::new(
+                span,
+                format_args!("`{}` is not supported for functions", caption),
+            ));
+        }
+        let mut struct_info = Self::with_builder_attr(ast, None, builder_attr, fields)?;
+        struct_info.function = Some(function);
+        struct_info.self_type = self_type;
+        Ok(struct_info)
+    }
+
+    /// Rejects the settings that generate methods on the built type itself, for builders of things
+    /// that are not a type of their own.
+    fn check_settings_of_types(builder_attr: &TypeBuilderAttr, span: Span, target: &str) -> syn::Result<()> {
+        let unsupported = [
+            ("apply_method", builder_attr.apply_method.as_ref().map(|_| span)),
+            ("merge_from_method", builder_attr.merge_from_method.as_ref().map(|_| span)),
+            ("partial_type", builder_attr.partial_type.as_ref().map(|_| span)),
+            ("constructor", builder_attr.constructors.first().map(|(name, _)| name.span())),
+            ("invariant", builder_attr.invariants.first().map(|invariant| invariant.span())),
+            ("reset", builder_attr.reset),
+        ];
+        if let Some((caption, span)) = unsupported.iter().find_map(|(caption, span)| Some((caption, (*span)?))) {
+            return Err(syn::Error::new(
+                span,
+                format_args!("`{}` is not supported for {}", caption, target),
+            ));
+        }
+        Ok(())
+    }
+
+    fn with_builder_attr(
+        ast: &'a syn::DeriveInput,
+        variant: Option<&'a syn::Ident>,
+        builder_attr: TypeBuilderAttr<'a>,
+        fields: impl Iterator<Item = &'a syn::Field>,
+    ) -> syn::Result<StructInfo<'a>> {
         let builder_name = builder_attr
             .builder_type
+            .common
             .get_name()
             .map(|name| strip_raw_ident_prefix(name.to_string()))
-            .unwrap_or_else(|| strip_raw_ident_prefix(format!("{}Builder", ast.ident)));
+            .unwrap_or_else(|| Self::default_type_name(&ast.ident, variant, "Builder"));
+        let mut fields: Vec<FieldInfo> = fields
+            .enumerate()
+            .map(|(i, f)| FieldInfo::new(i, f, builder_attr.field_defaults.clone()))
+            .collect::<Result<_, _>>()?;
+        // The errors of deferred `try_into` setters are returned by the build method.
+        let deferred_error =
+            (builder_attr.build_method.error.as_ref()).or(builder_attr.build_method.post.as_ref().map(|post| &post.error_type));
+        for field in fields.iter_mut() {
+            let Some(deferred) = field.builder_attr.setter.try_into_deferred else {
+                continue;
+            };
+            let Some(deferred_error) = deferred_error else {
+                return Err(syn::Error::new(
+                    deferred,
+                    "`try_into(deferred)` requires an error type for the build method - set `build_method(error = ...)`",
+                ));
+            };
+            field.deferred_error = Some(deferred_error.clone());
+            let unsupported = [
+                ("build_method(or_default)", builder_attr.build_method.or_default),
+                ("apply_method", builder_attr.apply_method.as_ref().map(|_| deferred)),
+                (
+                    "recipe(capture_defaults)",
+                    builder_attr.recipe.as_ref().and_then(|recipe| recipe.capture_defaults),
+                ),
+                ("constructor", builder_attr.constructors.first().map(|(name, _)| name.span())),
+                (
+                    "short_circuit",
+                    builder_attr.short_circuit.as_ref().map(|short_circuit| short_circuit.span),
+                ),
+            ];
+            if let Some((caption, span)) = unsupported.iter().find_map(|(caption, span)| Some((caption, (*span)?))) {
+                let mut error = syn::Error::new(span, format_args!("`{}` conflicts with `try_into(deferred)`", caption));
+                error.combine(syn::Error::new(deferred, "`try_into(deferred)` set here"));
+                return Err(error);
+            }
+        }
+        // The errors of `default_try` defaults are returned by the build method too, so they cannot
+        // be resolved anywhere else.
+        for field in fields.iter() {
+            let Some(default_try) = field.builder_attr.default_try else {
+                continue;
+            };
+            if deferred_error.is_none() {
+                return Err(syn::Error::new(
+                    default_try,
+                    "`default_try` requires an error type for the build method - set `build_method(error = ...)`",
+                ));
+            }
+            let unsupported = [
+                ("build_method(or_default)", builder_attr.build_method.or_default),
+                (
+                    "apply_method(defaults)",
+                    builder_attr
+                        .apply_method
+                        .as_ref()
+                        .and_then(|apply_method| apply_method.defaults),
+                ),
+                (
+                    "recipe(capture_defaults)",
+                    builder_attr.recipe.as_ref().and_then(|recipe| recipe.capture_defaults),
+                ),
+            ];
+            if let Some((caption, span)) = unsupported.iter().find_map(|(caption, span)| Some((caption, (*span)?))) {
+                let mut error = syn::Error::new(span, format_args!("`{}` conflicts with `default_try`", caption));
+                error.combine(syn::Error::new(default_try, "`default_try` set here"));
+                return Err(error);
+            }
+        }
+        // The value of `default_once` is shared by all the builds, so it cannot depend on the other
+        // fields of any one of them.
+        for field in fields.iter() {
+            let Some(default) = &field.builder_attr.default_once_expr else {
+                continue;
+            };
+            let other_fields = fields
+                .iter()
+                .filter(|f| f.ordinal != field.ordinal)
+                .map(|f| f.name)
+                .collect::<Vec<_>>();
+            if let Some(other_field) = find_variable_use(default.to_token_stream(), &other_fields) {
+                return Err(syn::Error::new_spanned(
+                    default,
+                    format!(
+                        "`default_once` cannot refer to other fields (found `{}`) - it is evaluated once for all the builds",
+                        other_field
+                    ),
+                ));
+            }
+        }
+        if builder_attr.context.is_some() {
+            if let Some(field) = fields.iter().find(|field| field.name == "context") {
+                return Err(syn::Error::new_spanned(
+                    field.name,
+                    "A field named `context` would shadow the builder's context",
+                ));
+            }
+        }
+        for name in builder_attr.builder_type.debug_skip.iter() {
+            if !fields.iter().any(|field| field.name == name) {
+                return Err(syn::Error::new_spanned(name, "no such field"));
+            }
+        }
+        if let Some(max_fields) = &builder_attr.max_fields {
+            if max_fields.base10_parse::<usize>()? < fields.len() {
+                return Err(syn::Error::new_spanned(
+                    &ast.ident,
+                    format!(
+                        "`{}` has {} fields, more than the `max_fields = {}` limit",
+                        ast.ident,
+                        fields.len(),
+                        max_fields
+                    ),
+                ));
+            }
+        }
+        // Rust drops fields in declaration order, so the requested drop order can only be verified.
+        let mut previous: Option<(&syn::Ident, usize)> = None;
+        for name in builder_attr.drop_order.iter() {
+            let Some(position) = fields.iter().position(|field| field.name == name) else {
+                return Err(syn::Error::new_spanned(name, "no such field"));
+            };
+            if let Some((previous_name, previous_position)) = previous {
+                if position <= previous_position {
+                    return Err(syn::Error::new_spanned(
+                        name,
+                        format!(
+                            "`{}` must be declared after `{}` to be dropped after it - fields are dropped in declaration order",
+                            name, previous_name
+                        ),
+                    ));
+                }
+            }
+            previous = Some((name, position));
+        }
+        let mut seeded_fields = Vec::new();
+        for param in builder_attr.builder_method.params.iter() {
+            let syn::Pat::Ident(syn::PatIdent { ident, subpat: None, .. }) = &*param.pat else {
+                return Err(syn::Error::new_spanned(&param.pat, "expected the name of a field"));
+            };
+            if !fields
+                .iter()
+                .any(|field| field.name == ident && field.builder_attr.setter.skip.is_none())
+            {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "builder method parameters must be named after a field with a setter, which they set",
+                ));
+            }
+            if seeded_fields.contains(&ident) {
+                return Err(syn::Error::new_spanned(ident, "duplicate builder method parameter"));
+            }
+            seeded_fields.push(ident);
+        }
+        if let Some(field) = builder_attr
+            .short_circuit
+            .as_ref()
+            .and_then(|short_circuit| short_circuit.field.as_ref())
+        {
+            if !fields.iter().any(|f| f.name == field) {
+                return Err(syn::Error::new_spanned(field, "no such field"));
+            }
+        }
+        if let Some(reset) = builder_attr.reset {
+            if builder_attr.context.is_some() || !builder_attr.builder_method.params.is_empty() {
+                return Err(syn::Error::new(
+                    reset,
+                    "`reset` requires a builder method without parameters - it cannot be combined with `context` or `builder_method(params(...))`",
+                ));
+            }
+        }
+        if let (Some(build_via), Some(invariant)) = (&builder_attr.build_via, builder_attr.invariants.first()) {
+            let mut error = syn::Error::new_spanned(invariant, "`invariant` cannot be combined with `build_via`");
+            error.combine(syn::Error::new_spanned(build_via, "build_via set here"));
+            return Err(error);
+        }
+        for (constructor_name, constructor_fields) in builder_attr.constructors.iter() {
+            for (i, name) in constructor_fields.iter().enumerate() {
+                if !fields.iter().any(|field| {
+                    field.name == name && field.builder_attr.setter.skip.is_none() && field.builder_attr.via_mutators.is_none()
+                }) {
+                    return Err(syn::Error::new_spanned(
+                        name,
+                        "constructor parameters must be fields with a setter",
+                    ));
+                }
+                if constructor_fields[..i].contains(name) {
+                    return Err(syn::Error::new_spanned(name, "duplicate constructor parameter"));
+                }
+            }
+            if let Some(field) = fields.iter().find(|field| {
+                field.builder_attr.setter.skip.is_none()
+                    && field.builder_attr.via_mutators.is_none()
+                    && field.builder_attr.default.is_none()
+                    && !constructor_fields.contains(field.name)
+            }) {
+                return Err(syn::Error::new_spanned(
+                    constructor_name,
+                    format!(
+                        "constructor `{}` must take the required field `{}`",
+                        constructor_name, field.name
+                    ),
+                ));
+            }
+        }
         Ok(StructInfo {
             vis: &ast.vis,
             name: &ast.ident,
+            variant,
+            tuple_struct: matches!(
+                &ast.data,
+                syn::Data::Struct(syn::DataStruct {
+                    fields: syn::Fields::Unnamed(_),
+                    ..
+                })
+            ),
+            function: None,
+            self_type: None,
             generics: &ast.generics,
-            fields: fields
-                .enumerate()
-                .map(|(i, f)| FieldInfo::new(i, f, builder_attr.field_defaults.clone()))
-                .collect::<Result<_, _>>()?,
+            fields,
             builder_attr,
             builder_name: syn::Ident::new(&builder_name, proc_macro2::Span::call_site()),
         })
@@ -75,14 +494,12 @@ impl<'a> StructInfo<'a> {
             ..
         } = *self;
         let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
-        let init_fields_type = type_tuple(self.included_fields().map(|f| {
-            if f.builder_attr.via_mutators.is_some() {
-                f.tuplized_type_ty_param()
-            } else {
-                empty_type()
-            }
-        }));
+        let init_fields_type = self.init_fields_type();
         let init_fields_expr = self.included_fields().map(|f| {
+            if self.is_seeded_by_builder_method(f) {
+                let name = f.name;
+                return quote!((#name,));
+            }
             f.builder_attr.via_mutators.as_ref().map_or_else(
                 || quote!(()),
                 |via_mutators| {
@@ -94,7 +511,7 @@ impl<'a> StructInfo<'a> {
         let mut all_fields_param_type: syn::TypeParam =
             syn::Ident::new("TypedBuilderFields", proc_macro2::Span::call_site()).into();
         let all_fields_param = syn::GenericParam::Type(all_fields_param_type.clone());
-        all_fields_param_type.default = Some(syn::Type::Tuple(init_fields_type.clone()));
+        all_fields_param_type.default = self.init_fields_type_default().map(syn::Type::Tuple);
         let b_generics = {
             let mut generics = self.generics.clone();
             generics.params.push(syn::GenericParam::Type(all_fields_param_type));
@@ -103,32 +520,41 @@ impl<'a> StructInfo<'a> {
         let generics_with_empty = modify_types_generics_hack(&ty_generics, |args| {
             args.push(syn::GenericArgument::Type(init_fields_type.clone().into()));
         });
-        let phantom_generics = self.generics.params.iter().filter_map(|param| match param {
-            syn::GenericParam::Lifetime(lifetime) => {
-                let lifetime = &lifetime.lifetime;
-                Some(quote!(&#lifetime ()))
-            }
-            syn::GenericParam::Type(ty) => {
-                let ty = &ty.ident;
-                Some(ty.to_token_stream())
-            }
-            syn::GenericParam::Const(_cnst) => None,
-        });
+        let phantom = self.phantom_type();
 
-        let builder_method_name = self.builder_attr.builder_method.get_name().unwrap_or_else(|| quote!(builder));
+        let builder_method_name = self
+            .builder_attr
+            .builder_method
+            .common
+            .get_name()
+            .unwrap_or_else(|| self.default_builder_method_name());
         let builder_method_visibility = first_visibility(&[
-            self.builder_attr.builder_method.vis.as_ref(),
-            self.builder_attr.builder_type.vis.as_ref(),
+            self.builder_attr.builder_method.common.vis.as_ref(),
+            self.builder_attr.builder_type.common.vis.as_ref(),
             Some(vis),
         ]);
-        let builder_method_doc = self.builder_attr.builder_method.get_doc_or(|| {
+        let function_docs = self.function.filter(|_| self.self_type.is_none()).map(|function| {
+            function
+                .attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("doc"))
+                .collect::<Vec<_>>()
+        });
+        let builder_method_doc = self.builder_attr.builder_method.common.get_doc_or(|| {
+            let (purpose, finish) = if self.function.is_some() {
+                ("calling", "call")
+            } else {
+                ("building", "create the instance of")
+            };
             format!(
                 "
-                Create a builder for building `{name}`.
+                Create a builder for {purpose} `{name}`.
                 On the builder, call {setters} to set the values of the fields.
-                Finally, call `.{build_method_name}()` to create the instance of `{name}`.
+                Finally, call `.{build_method_name}()` to {finish} `{name}`.
                 ",
-                name = self.name,
+                purpose = purpose,
+                finish = finish,
+                name = self.display_name(),
                 build_method_name = self.build_method_name(),
                 setters = {
                     let mut result = String::new();
@@ -150,16 +576,17 @@ impl<'a> StructInfo<'a> {
             )
         });
 
-        let builder_type_visibility = first_visibility(&[self.builder_attr.builder_type.vis.as_ref(), Some(vis)]);
+        let builder_type_visibility = first_visibility(&[self.builder_attr.builder_type.common.vis.as_ref(), Some(vis)]);
         let builder_type_doc = if self.builder_attr.doc {
-            self.builder_attr.builder_type.get_doc_or(|| {
+            self.builder_attr.builder_type.common.get_doc_or(|| {
                 format!(
                     "
                     Builder for [`{name}`] instances.
 
-                    See [`{name}::{builder_method_name}()`] for more info.
+                    See [`{type_name}::{builder_method_name}()`] for more info.
                     ",
-                    name = name,
+                    name = self.display_name(),
+                    type_name = name,
                     builder_method_name = builder_method_name
                 )
             })
@@ -174,26 +601,96 @@ impl<'a> StructInfo<'a> {
         if let Some(predicates) = b_generics_where_extras_predicates {
             b_generics_where.predicates.extend(predicates.predicates.clone());
         }
+        let impl_traits = self.builder_attr.builder_type.impl_traits.iter().map(|trait_path| {
+            quote! {
+                #[automatically_derived]
+                impl #b_generics_impl #trait_path for #builder_name #b_generics_ty #b_generics_where_extras_predicates {}
+            }
+        });
 
-        Ok(quote! {
-            #[automatically_derived]
-            impl #impl_generics #name #ty_generics #where_clause {
-                #builder_method_doc
-                #[allow(dead_code, clippy::default_trait_access)]
-                #builder_method_visibility fn #builder_method_name() -> #builder_name #generics_with_empty {
-                    #builder_name {
-                        fields: (#(#init_fields_expr,)*),
-                        phantom: ::core::default::Default::default(),
-                    }
+        let builder_method_params = &self.builder_attr.builder_method.params;
+        let (context_param, context_init, context_field, context_clone) = if let Some(context) = &self.builder_attr.context {
+            (
+                quote!(context: #context,),
+                quote!(context,),
+                quote!(context: #context,),
+                quote!(context: ::core::clone::Clone::clone(&self.context),),
+            )
+        } else {
+            (quote!(), quote!(), quote!(), quote!())
+        };
+
+        let mutator_requirements = self.mutator_requirements_impl(&generics_with_empty)?;
+        let schema = self.schema_impl(&generics_with_empty)?;
+        let reset_methods = self.builder_attr.reset.map(|_| {
+            let fresh_builder_doc = format!("Create a builder for building `{}` with none of the fields set.", name);
+            let reset_doc = format!(
+                "Discard this `{}` and create a builder with none of the fields set, e.g. for reusing pooled objects.",
+                name
+            );
+            quote! {
+                #[doc = #fresh_builder_doc]
+                #[allow(dead_code)]
+                #builder_method_visibility fn into_fresh_builder() -> #builder_name #generics_with_empty {
+                    Self::#builder_method_name()
+                }
+
+                #[doc = #reset_doc]
+                #[allow(dead_code, clippy::needless_pass_by_value)]
+                #builder_method_visibility fn reset(self) -> #builder_name #generics_with_empty {
+                    Self::#builder_method_name()
+                }
+            }
+        });
+
+        // For functions, the builder method is a free function that takes the function's place.
+        let (builder_method_doc, builder_method_generics, builder_method_where_clause) = match &function_docs {
+            Some(docs) if !docs.is_empty() && self.builder_attr.builder_method.common.doc.is_none() => {
+                (quote!(#(#docs)*), Some(&impl_generics), where_clause)
+            }
+            Some(_) => (builder_method_doc, Some(&impl_generics), where_clause),
+            None => (builder_method_doc, None, None),
+        };
+        let builder_method = quote! {
+            #builder_method_doc
+            #[allow(dead_code, clippy::default_trait_access)]
+            #builder_method_visibility fn #builder_method_name #builder_method_generics (#context_param #(#builder_method_params),*) -> #builder_name #generics_with_empty #builder_method_where_clause {
+                #builder_name {
+                    fields: (#(#init_fields_expr,)*),
+                    #context_init
+                    phantom: ::core::default::Default::default(),
+                }
+            }
+        };
+        let builder_method = if let Some(self_type) = self.self_type {
+            quote! {
+                #[automatically_derived]
+                impl #impl_generics #self_type #where_clause {
+                    #builder_method
+                }
+            }
+        } else if self.function.is_some() {
+            builder_method
+        } else {
+            quote! {
+                #[automatically_derived]
+                impl #impl_generics #name #ty_generics #where_clause {
+                    #builder_method
+                    #reset_methods
                 }
             }
+        };
+
+        Ok(quote! {
+            #builder_method
 
             #[must_use]
             #builder_type_doc
             #[allow(dead_code, non_camel_case_types, non_snake_case)]
             #builder_type_visibility struct #builder_name #b_generics #b_generics_where_extras_predicates {
                 fields: #all_fields_param,
-                phantom: ::core::marker::PhantomData<(#( ::core::marker::PhantomData<#phantom_generics> ),*)>,
+                #context_field
+                phantom: #phantom,
             }
 
             #[automatically_derived]
@@ -202,31 +699,118 @@ impl<'a> StructInfo<'a> {
                 fn clone(&self) -> Self {
                     Self {
                         fields: self.fields.clone(),
+                        #context_clone
                         phantom: ::core::default::Default::default(),
                     }
                 }
             }
+
+            #( #impl_traits )*
+
+            #mutator_requirements
+            #schema
+        })
+    }
+
+    /// A human-readable description of the setters, one per line, for `emit_schema`.
+    fn schema_impl(&self, initial_generics: &syn::AngleBracketedGenericArguments) -> syn::Result<TokenStream> {
+        if self.builder_attr.emit_schema.is_none() {
+            return Ok(quote!());
+        }
+        let mut schema = String::new();
+        for field in self.setter_fields() {
+            use std::fmt::Write;
+            let (param_list, _) = field.setter_param_list_and_value(&self.builder_attr.crate_module_path)?;
+            let mut params = field.token_param();
+            params.extend(param_list);
+            let generic = match field.fallible_setter_generic_and_error()? {
+                Some((generic, _)) => generic,
+                None => field.named_into_generic()?.or(field.deferred_try_into_generic()?),
+            };
+            let asyncness = if field.builder_attr.setter.async_into.is_some() {
+                "async "
+            } else {
+                ""
+            };
+            write!(
+                &mut schema,
+                "{}{}{}({})",
+                asyncness,
+                field.setter_method_name(),
+                generic.as_ref().map(tokens_to_readable_string).unwrap_or_default(),
+                tokens_to_readable_string(&params),
+            )
+            .unwrap();
+            if field.fallible_setter_generic_and_error()?.is_some() {
+                write!(&mut schema, " -> Result").unwrap();
+            }
+            if field.builder_attr.default.is_some() {
+                write!(&mut schema, " (optional)").unwrap();
+            }
+            writeln!(&mut schema).unwrap();
+        }
+        let builder_name = &self.builder_name;
+        let (impl_generics, _, where_clause) = self.generics.split_for_impl();
+        Ok(quote! {
+            #[automatically_derived]
+            impl #impl_generics #builder_name #initial_generics #where_clause {
+                /// A description of the setters of the builder, one per line.
+                #[allow(dead_code)]
+                pub const SCHEMA: &'static str = #schema;
+            }
+        })
+    }
+
+    /// A table of the mutators that can be called on the builder and the fields each of them
+    /// requires. It is on the builder in its initial state, so that `FooBuilder::MUTATOR_REQUIREMENTS`
+    /// does not need the type-state to be spelled out.
+    fn mutator_requirements_impl(&self, initial_generics: &syn::AngleBracketedGenericArguments) -> syn::Result<TokenStream> {
+        let mutators = self.mutators().filter(|m| m.auto.is_none()).collect::<Vec<_>>();
+        if mutators.is_empty() {
+            return Ok(quote!());
+        }
+        let builder_name = &self.builder_name;
+        let (impl_generics, _, where_clause) = self.generics.split_for_impl();
+        let entries = mutators
+            .into_iter()
+            .map(|mutator| {
+                let required_fields = self.resolve_mutator_requirements(&mutator.required_fields)?;
+                let mutator_name = mutator.fun.sig.ident.to_string();
+                let field_names = self
+                    .included_fields()
+                    .filter(|f| f.builder_attr.via_mutators.is_none() && required_fields.contains(f.name))
+                    .map(|f| strip_raw_ident_prefix(f.name.to_string()));
+                Ok(quote!((#mutator_name, &[#( #field_names ),*])))
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+        Ok(quote! {
+            #[automatically_derived]
+            impl #impl_generics #builder_name #initial_generics #where_clause {
+                /// The mutators of the builder, and the fields each of them requires to be set before it can be called.
+                #[allow(dead_code)]
+                pub const MUTATOR_REQUIREMENTS: &'static [(&'static str, &'static [&'static str])] = &[#( #entries ),*];
+            }
         })
     }
 
     fn field_impl(&self, field: &FieldInfo) -> syn::Result<TokenStream> {
         let StructInfo { ref builder_name, .. } = *self;
 
-        let descructuring = self.included_fields().map(|f| {
-            if f.ordinal == field.ordinal {
-                quote!(())
-            } else {
-                let name = f.name;
-                name.to_token_stream()
-            }
-        });
-        let reconstructing = self.included_fields().map(|f| f.name);
+        let descructuring = self
+            .included_fields()
+            .map(|f| {
+                if f.ordinal == field.ordinal {
+                    quote!(())
+                } else {
+                    let name = f.name;
+                    name.to_token_stream()
+                }
+            })
+            .collect::<Vec<_>>();
+        let reconstructing = self.included_fields().map(|f| f.name).collect::<Vec<_>>();
+        let context_passthrough = self.context_passthrough();
 
-        let &FieldInfo {
-            name: field_name,
-            ty: field_type,
-            ..
-        } = field;
+        let &FieldInfo { name: field_name, .. } = field;
         let mut ty_generics = self.generic_arguments();
         let mut target_generics_tuple = empty_type_tuple();
         let mut ty_generics_tuple = empty_type_tuple();
@@ -254,31 +838,18 @@ impl<'a> StructInfo<'a> {
         let doc = field.builder_attr.setter.doc.as_ref().map(|doc| quote!(#[doc = #doc]));
         let deprecated = &field.builder_attr.deprecated;
 
-        // NOTE: both auto_into and strip_option affect `arg_type` and `arg_expr`, but the order of
-        // nesting is different so we have to do this little dance.
-        let arg_type = if field.builder_attr.setter.strip_option.is_some() && field.builder_attr.setter.transform.is_none() {
-            field
-                .type_from_inside_option()
-                .ok_or_else(|| Error::new_spanned(field_type, "can't `strip_option` - field is not `Option<...>`"))?
-        } else {
-            field_type
-        };
-        let (arg_type, arg_expr) = if field.builder_attr.setter.auto_into.is_some() {
-            (quote!(impl ::core::convert::Into<#arg_type>), quote!(#field_name.into()))
-        } else {
-            (arg_type.to_token_stream(), field_name.to_token_stream())
-        };
-
-        let (param_list, arg_expr) = if field.builder_attr.setter.strip_bool.is_some() {
-            (quote!(), quote!(true))
-        } else if let Some(transform) = &field.builder_attr.setter.transform {
-            let params = transform.params.iter().map(|(pat, ty)| quote!(#pat: #ty));
-            let body = &transform.body;
-            (quote!(#(#params),*), quote!({ #body }))
-        } else if field.builder_attr.setter.strip_option.is_some() {
-            (quote!(#field_name: #arg_type), quote!(Some(#arg_expr)))
-        } else {
-            (quote!(#field_name: #arg_type), arg_expr)
+        let (param_list, arg_expr) = field.setter_param_list_and_value(&self.builder_attr.crate_module_path)?;
+        let (setter_generic, setter_return_type, setter_return_wrapper) = match field.fallible_setter_generic_and_error()? {
+            Some((generic, error_type)) => (
+                generic,
+                quote!(::core::result::Result<#builder_name <#target_generics>, #error_type>),
+                quote!(::core::result::Result::Ok),
+            ),
+            None => (
+                field.named_into_generic()?.or(field.deferred_try_into_generic()?),
+                quote!(#builder_name <#target_generics>),
+                quote!(),
+            ),
         };
 
         let repeated_fields_error_type_name = syn::Ident::new(
@@ -292,41 +863,565 @@ impl<'a> StructInfo<'a> {
         let repeated_fields_error_message = format!("Repeated field {}", field_name);
 
         let method_name = field.setter_method_name();
+        let token_param = field.token_param();
+        let asyncness = field.setter_asyncness();
+        if let Some(generic) = field
+            .builder_attr
+            .setter
+            .auto_into
+            .as_ref()
+            .and_then(|auto_into| auto_into.generic.as_ref())
+        {
+            // Lifetimes are in their own namespace, and `generic` is parsed as an identifier, so it
+            // cannot collide with them.
+            if self.generics.type_params().any(|param| param.ident == *generic)
+                || self.generics.const_params().any(|param| param.ident == *generic)
+            {
+                return Err(syn::Error::new_spanned(
+                    generic,
+                    format!(
+                        "the setter generic `{}` collides with a generic parameter of the struct",
+                        generic
+                    ),
+                ));
+            }
+            if let Some(f) = self.included_fields().find(|f| f.generic_ident == *generic) {
+                return Err(syn::Error::new_spanned(
+                    generic,
+                    format!(
+                        "the setter generic `{}` collides with the builder's generic parameter for the field `{}`",
+                        generic, f.name
+                    ),
+                ));
+            }
+        }
 
-        Ok(quote! {
-            #[allow(dead_code, non_camel_case_types, missing_docs)]
-            #[automatically_derived]
-            impl #impl_generics #builder_name <#ty_generics> #where_clause {
+        let field_mut = field.builder_attr.setter.field_mut.map(|_| {
+            let field_mut_method_name = field.field_mut_method_name();
+            let field_type = field.ty;
+            let index = syn::Index::from(self.included_fields().take_while(|f| f.ordinal != field.ordinal).count());
+            let doc = format!("Mutable access to the already set value of `{}`.", field_name);
+            quote! {
+                #[allow(dead_code, non_camel_case_types, missing_docs)]
+                #[automatically_derived]
+                impl #impl_generics #builder_name <#target_generics> #where_clause {
+                    #[doc = #doc]
+                    pub fn #field_mut_method_name (&mut self) -> &mut #field_type {
+                        &mut self.fields.#index.0
+                    }
+                }
+            }
+        });
+
+        let each_setter = if let Some((each_param_list, each_item)) = field.each_setter_param_list_and_item()? {
+            let each = field
+                .builder_attr
+                .setter
+                .each
+                .as_ref()
+                .expect("each setter params without each setting");
+            let each_name = each.name.as_ref().expect("each setting without a name");
+            if *each_name == method_name {
+                return Err(syn::Error::new(
+                    each.span,
+                    "the `each` setter cannot have the same name as the field's setter",
+                ));
+            }
+            let field_type = field.ty;
+            let seed = self.seed_value(field, "each")?;
+            let index = syn::Index::from(self.included_fields().take_while(|f| f.ordinal != field.ordinal).count());
+            let each_doc = if let Some(max) = &each.max {
+                format!(
+                    "Add an item to `{}`. Panics if `{}` would have more than {} items.",
+                    field_name, field_name, max
+                )
+            } else {
+                format!("Add an item to `{}`. Can be called any number of times.", field_name)
+            };
+            let max_check = |items: TokenStream| {
+                each.max.as_ref().map(|max| {
+                    let message = format!("`{}` can have at most {} items", field_name, max);
+                    quote!(::core::assert!(#items.len() < #max, #message);)
+                })
+            };
+            let unset_max_check = max_check(field_name.to_token_stream());
+            let set_max_check = max_check(quote!(self.fields.#index.0));
+            Some(quote! {
+                #[allow(dead_code, non_camel_case_types, missing_docs)]
+                #[automatically_derived]
+                impl #impl_generics #builder_name <#ty_generics> #where_clause {
+                    #deprecated
+                    #[doc = #each_doc]
+                    #[allow(clippy::used_underscore_binding, clippy::no_effect_underscore_binding)]
+                    pub fn #each_name (self, #token_param #each_param_list) -> #builder_name <#target_generics> {
+                        let mut #field_name: #field_type = #seed;
+                        #unset_max_check
+                        #field_name.push(#each_item);
+                        let #field_name = (#field_name,);
+                        let ( #(#descructuring,)* ) = self.fields;
+                        #builder_name {
+                            fields: ( #(#reconstructing,)* ),
+                            #context_passthrough
+                            phantom: self.phantom,
+                        }
+                    }
+                }
+                #[allow(dead_code, non_camel_case_types, missing_docs)]
+                #[automatically_derived]
+                impl #impl_generics #builder_name <#target_generics> #where_clause {
+                    #deprecated
+                    #[doc = #each_doc]
+                    pub fn #each_name (mut self, #token_param #each_param_list) -> Self {
+                        #set_max_check
+                        self.fields.#index.0.push(#each_item);
+                        self
+                    }
+                }
+            })
+        } else {
+            None
+        };
+
+        let entry_setter = if let Some((entry_param_list, key, value)) = field.entry_setter_param_list_key_and_value()? {
+            let entry = field
+                .builder_attr
+                .setter
+                .entry
+                .as_ref()
+                .expect("entry setter params without entry setting");
+            let entry_name = entry.name.as_ref().expect("entry setting without a name");
+            let each_name = field.builder_attr.setter.each.as_ref().and_then(|each| each.name.as_ref());
+            if *entry_name == method_name || Some(entry_name) == each_name {
+                return Err(syn::Error::new(
+                    entry.span,
+                    "the `entry` setter cannot have the same name as another setter of the field",
+                ));
+            }
+            let field_type = field.ty;
+            let seed = self.seed_value(field, "entry")?;
+            let index = syn::Index::from(self.included_fields().take_while(|f| f.ordinal != field.ordinal).count());
+            let entry_doc = format!(
+                "Insert a key and a value into `{}`. Can be called any number of times - a repeated key replaces the value.",
+                field_name
+            );
+            Some(quote! {
+                #[allow(dead_code, non_camel_case_types, missing_docs)]
+                #[automatically_derived]
+                impl #impl_generics #builder_name <#ty_generics> #where_clause {
+                    #deprecated
+                    #[doc = #entry_doc]
+                    #[allow(clippy::used_underscore_binding, clippy::no_effect_underscore_binding)]
+                    pub fn #entry_name (self, #token_param #entry_param_list) -> #builder_name <#target_generics> {
+                        let mut #field_name: #field_type = #seed;
+                        #field_name.insert(#key, #value);
+                        let #field_name = (#field_name,);
+                        let ( #(#descructuring,)* ) = self.fields;
+                        #builder_name {
+                            fields: ( #(#reconstructing,)* ),
+                            #context_passthrough
+                            phantom: self.phantom,
+                        }
+                    }
+                }
+                #[allow(dead_code, non_camel_case_types, missing_docs)]
+                #[automatically_derived]
+                impl #impl_generics #builder_name <#target_generics> #where_clause {
+                    #deprecated
+                    #[doc = #entry_doc]
+                    pub fn #entry_name (mut self, #token_param #entry_param_list) -> Self {
+                        self.fields.#index.0.insert(#key, #value);
+                        self
+                    }
+                }
+            })
+        } else {
+            None
+        };
+
+        let indexed_setters = self.indexed_setters_impl(field)?;
+
+        let setter_fn = if field.builder_attr.setter.setter_fn.is_some() {
+            let setter_fn_method_name = field.setter_fn_method_name();
+            let mut arg_types = field.setter_fn_arg_types()?;
+            if let Some(token) = &field.builder_attr.setter.requires_token {
+                arg_types.insert(0, token.to_token_stream());
+            }
+            let arg_names = (0..arg_types.len()).map(|i| format_ident!("arg{}", i)).collect::<Vec<_>>();
+            let doc = format!(
+                "The `{}` setter as a function value, that can be passed to higher-order functions.",
+                method_name
+            );
+            Some(quote! {
+                #[doc = #doc]
+                #[allow(deprecated)]
+                pub fn #setter_fn_method_name () -> impl ::core::ops::FnOnce(Self, #(#arg_types),*) -> #builder_name <#target_generics> {
+                    |builder: Self, #(#arg_names: #arg_types),*| builder.#method_name(#(#arg_names),*)
+                }
+            })
+        } else {
+            None
+        };
+
+        // With `merge` or `extend`, calling the setter again extends the value instead of being an error.
+        let repeated_setter = if field.builder_attr.setter.merge.is_some() || field.builder_attr.setter.extend.is_some() {
+            let index = syn::Index::from(self.included_fields().take_while(|f| f.ordinal != field.ordinal).count());
+            // The items of `extend` are added directly, rather than through the collection a first call
+            // collects them into.
+            let items = if field.builder_attr.setter.extend.is_some() {
+                field_name.to_token_stream()
+            } else {
+                arg_expr.clone()
+            };
+            quote! {
+                #[allow(dead_code, non_camel_case_types, missing_docs)]
+                #[automatically_derived]
+                impl #impl_generics #builder_name <#target_generics> #where_clause {
+                    #deprecated
+                    #doc
+                    #[allow(clippy::used_underscore_binding, clippy::no_effect_underscore_binding)]
+                    pub fn #method_name #setter_generic (mut self, #token_param #param_list) -> Self {
+                        ::core::iter::Extend::extend(&mut self.fields.#index.0, #items);
+                        self
+                    }
+                }
+            }
+        } else {
+            quote! {
+                #[doc(hidden)]
+                #[allow(dead_code, non_camel_case_types, non_snake_case)]
+                #[allow(clippy::exhaustive_enums)]
+                pub enum #repeated_fields_error_type_name {}
+                #[doc(hidden)]
+                #[allow(dead_code, non_camel_case_types, missing_docs)]
+                #[automatically_derived]
+                impl #impl_generics #builder_name <#target_generics> #where_clause {
+                    #[deprecated(
+                        note = #repeated_fields_error_message
+                    )]
+                    pub fn #method_name (self, _: #repeated_fields_error_type_name) -> #builder_name <#target_generics> {
+                        self
+                    }
+                }
+            }
+        };
+
+        let maybe_into_setter = if field.builder_attr.setter.maybe_into.is_some() {
+            let maybe_into_method_name = field.maybe_into_method_name();
+            if let Some(colliding) = self.setter_fields().find(|f| {
+                f.setter_method_name() == maybe_into_method_name
+                    || f.builder_attr.setter.each.as_ref().and_then(|each| each.name.as_ref()) == Some(&maybe_into_method_name)
+                    || f.builder_attr.setter.entry.as_ref().and_then(|entry| entry.name.as_ref()) == Some(&maybe_into_method_name)
+            }) {
+                return Err(syn::Error::new_spanned(
+                    colliding.name,
+                    format!(
+                        "the `maybe_into` setter `{}` of `{}` collides with a setter of this field",
+                        maybe_into_method_name, field_name
+                    ),
+                ));
+            }
+            let (maybe_into_param_list, maybe_into_value) = field.maybe_into_param_list_and_value()?;
+            let doc = format!("Like `{}`, but converts the argument with `Into`.", method_name);
+            Some(quote! {
                 #deprecated
-                #doc
+                #[doc = #doc]
                 #[allow(clippy::used_underscore_binding, clippy::no_effect_underscore_binding)]
-                pub fn #method_name (self, #param_list) -> #builder_name <#target_generics> {
-                    let #field_name = (#arg_expr,);
+                pub fn #maybe_into_method_name (self, #token_param #maybe_into_param_list) -> #builder_name <#target_generics> {
+                    let #field_name = (#maybe_into_value,);
                     let ( #(#descructuring,)* ) = self.fields;
                     #builder_name {
                         fields: ( #(#reconstructing,)* ),
+                        #context_passthrough
                         phantom: self.phantom,
                     }
                 }
-            }
-            #[doc(hidden)]
-            #[allow(dead_code, non_camel_case_types, non_snake_case)]
-            #[allow(clippy::exhaustive_enums)]
-            pub enum #repeated_fields_error_type_name {}
-            #[doc(hidden)]
+            })
+        } else {
+            None
+        };
+
+        let unit_setters = field
+            .builder_attr
+            .setter
+            .units
+            .iter()
+            .map(|unit| {
+                let unit_method_name = field.unit_method_name(unit);
+                if let Some(colliding) = self.setter_fields().find(|f| f.setter_method_name() == unit_method_name) {
+                    return Err(syn::Error::new_spanned(
+                        &unit.name,
+                        format!(
+                            "the `{}` setter of `{}` collides with the setter of `{}`",
+                            unit_method_name, field_name, colliding.name
+                        ),
+                    ));
+                }
+                let (unit_param_list, unit_value) = field.unit_param_list_and_value(unit)?;
+                let doc = format!("Like `{}`, but takes the value in `{}`.", method_name, unit.name);
+                Ok(quote! {
+                    #deprecated
+                    #[doc = #doc]
+                    #[allow(clippy::used_underscore_binding, clippy::no_effect_underscore_binding)]
+                    pub fn #unit_method_name (self, #token_param #unit_param_list) -> #builder_name <#target_generics> {
+                        let #field_name = (#unit_value,);
+                        let ( #(#descructuring,)* ) = self.fields;
+                        #builder_name {
+                            fields: ( #(#reconstructing,)* ),
+                            #context_passthrough
+                            phantom: self.phantom,
+                        }
+                    }
+                })
+            })
+            .collect::<syn::Result<TokenStream>>()?;
+
+        Ok(quote! {
+            #field_mut
+            #each_setter
+            #entry_setter
+            #indexed_setters
             #[allow(dead_code, non_camel_case_types, missing_docs)]
             #[automatically_derived]
-            impl #impl_generics #builder_name <#target_generics> #where_clause {
-                #[deprecated(
-                    note = #repeated_fields_error_message
-                )]
-                pub fn #method_name (self, _: #repeated_fields_error_type_name) -> #builder_name <#target_generics> {
-                    self
+            impl #impl_generics #builder_name <#ty_generics> #where_clause {
+                #deprecated
+                #doc
+                #[allow(clippy::used_underscore_binding, clippy::no_effect_underscore_binding)]
+                pub #asyncness fn #method_name #setter_generic (self, #token_param #param_list) -> #setter_return_type {
+                    let #field_name = (#arg_expr,);
+                    let ( #(#descructuring,)* ) = self.fields;
+                    #setter_return_wrapper(#builder_name {
+                        fields: ( #(#reconstructing,)* ),
+                        #context_passthrough
+                        phantom: self.phantom,
+                    })
                 }
+                #setter_fn
+                #maybe_into_setter
+                #unit_setters
             }
+            #repeated_setter
         })
     }
 
+    /// The value that an `each` or an `entry` setter adds to when the field was not set yet - the
+    /// field's default. The setter cannot see the other fields, so the default cannot refer to them.
+    fn seed_value(&self, field: &FieldInfo, setter_caption: &str) -> syn::Result<TokenStream> {
+        let Some(default) = &field.builder_attr.default else {
+            return Ok(quote!(::core::default::Default::default()));
+        };
+        let other_fields = self
+            .fields
+            .iter()
+            .filter(|f| f.ordinal != field.ordinal)
+            .map(|f| f.name)
+            .collect::<Vec<_>>();
+        if let Some(other_field) = find_variable_use(default.to_token_stream(), &other_fields) {
+            return Err(syn::Error::new_spanned(
+                default,
+                format!(
+                    "the default of a field with an `{}` setter cannot refer to other fields (found `{}`) - it is the initial value of the `{}` setter",
+                    setter_caption, other_field, setter_caption
+                ),
+            ));
+        }
+        Ok(default.to_token_stream())
+    }
+
+    /// The `setter(indexed)` setters, which set one element of an array field each.
+    ///
+    /// While only some of the elements are set, the field's slot in the builder's state is an
+    /// `Indexed` tuple with a `()` or a `(T,)` per element. Setting the last missing element turns
+    /// the slot into the regular `([T; N],)` of a set field - this is decided at the type level by
+    /// chaining `IndexSlot::Then` over the elements.
+    fn indexed_setters_impl(&self, field: &FieldInfo) -> syn::Result<Option<TokenStream>> {
+        let Some((item_type, len)) = field.indexed_setter_item_and_len()? else {
+            return Ok(None);
+        };
+        let StructInfo { ref builder_name, .. } = *self;
+        let crate_module_path = &self.builder_attr.crate_module_path;
+        let field_name = field.name;
+        let token_param = field.token_param();
+        let deprecated = &field.builder_attr.deprecated;
+        let context_passthrough = self.context_passthrough();
+        let method_name = field.setter_method_name();
+
+        let trait_name = syn::Ident::new(
+            &format!("{}_Indexed_{}", builder_name, strip_raw_ident_prefix(field_name.to_string())),
+            proc_macro2::Span::call_site(),
+        );
+        let all_set = (0..len).map(|_| quote!((__T,))).collect::<Vec<_>>();
+        let items = (0..len).map(syn::Index::from);
+        let trait_impl = quote! {
+            #[doc(hidden)]
+            #[allow(dead_code, non_camel_case_types)]
+            pub trait #trait_name<__States> {
+                type Slot;
+                fn slot(states: __States) -> Self::Slot;
+            }
+            #[automatically_derived]
+            impl<__T> #trait_name<( #(#all_set,)* )> for #crate_module_path::IndexAllSet {
+                type Slot = ([__T; #len],);
+                fn slot(states: ( #(#all_set,)* )) -> Self::Slot {
+                    ([ #(states.#items.0),* ],)
+                }
+            }
+            #[automatically_derived]
+            impl<__States> #trait_name<__States> for #crate_module_path::IndexUnset {
+                type Slot = #crate_module_path::Indexed<__States>;
+                fn slot(states: __States) -> Self::Slot {
+                    #crate_module_path::Indexed(states)
+                }
+            }
+        };
+
+        // The builder's state generics, with `slot` in the field's position.
+        let state_generics = |slot: syn::Type| {
+            let mut generics_tuple = empty_type_tuple();
+            for f in self.included_fields() {
+                generics_tuple.elems.push_value(if f.ordinal == field.ordinal {
+                    slot.clone()
+                } else {
+                    f.type_ident()
+                });
+                generics_tuple.elems.push_punct(Default::default());
+            }
+            let mut generics = self.generic_arguments();
+            generics.push(syn::GenericArgument::Type(generics_tuple.into()));
+            generics
+        };
+        let mut generics = self.generics.clone();
+        for f in self.included_fields() {
+            if f.ordinal != field.ordinal {
+                generics.params.push(f.generic_ty_param());
+            }
+        }
+        let reconstructing = self.included_fields().map(|f| f.name).collect::<Vec<_>>();
+
+        let setters = (0..len).map(|index| {
+            let setter_name = format_ident!("{}_{}", method_name, index);
+            let doc = format!("Set the element of `{}` at index {}.", field_name, index);
+            let state_params = (0..len)
+                .map(|i| format_ident!("__{}_index_{}", strip_raw_ident_prefix(field_name.to_string()), i))
+                .collect::<Vec<_>>();
+            let set_state: syn::Type = parse_quote!((#item_type,));
+            let states_type = |unset: &dyn Fn(&Ident) -> syn::Type| -> syn::Type {
+                let states = state_params
+                    .iter()
+                    .enumerate()
+                    .map(|(i, param)| if i == index { set_state.clone() } else { unset(param) });
+                parse_quote!(( #(#states,)* ))
+            };
+            let all_set_check = |states: &syn::Type| -> syn::Type {
+                let syn::Type::Tuple(states) = states else {
+                    unreachable!("element states are always a tuple")
+                };
+                states.elems.iter().rev().fold(
+                    parse_quote!(#crate_module_path::IndexAllSet),
+                    |rest: syn::Type, state| parse_quote!(<#state as #crate_module_path::IndexSlot>::Then<#rest>),
+                )
+            };
+
+            // From a fresh field.
+            let fresh_states = states_type(&|_| empty_type());
+            let fresh_check = all_set_check(&fresh_states);
+            let fresh_values = (0..len).map(|i| if i == index { quote!((value,)) } else { quote!(()) });
+            let fresh_generics = state_generics(empty_type());
+            let fresh_target = state_generics(parse_quote!(<#fresh_check as #trait_name<#fresh_states>>::Slot));
+            let descructuring = self.included_fields().map(|f| {
+                if f.ordinal == field.ordinal {
+                    quote!(())
+                } else {
+                    f.name.to_token_stream()
+                }
+            });
+            let (impl_generics, _, where_clause) = generics.split_for_impl();
+            let fresh_setter = quote! {
+                #[allow(dead_code, non_camel_case_types, missing_docs)]
+                #[automatically_derived]
+                impl #impl_generics #builder_name <#fresh_generics> #where_clause {
+                    #deprecated
+                    #[doc = #doc]
+                    #[allow(clippy::used_underscore_binding, clippy::no_effect_underscore_binding)]
+                    pub fn #setter_name (self, #token_param value: #item_type) -> #builder_name <#fresh_target> {
+                        let #field_name = <#fresh_check as #trait_name<#fresh_states>>::slot(( #(#fresh_values,)* ));
+                        let ( #(#descructuring,)* ) = self.fields;
+                        #builder_name {
+                            fields: ( #(#reconstructing,)* ),
+                            #context_passthrough
+                            phantom: self.phantom,
+                        }
+                    }
+                }
+            };
+
+            // From a field with some of the other elements set.
+            let partial_states = states_type(&|param| parse_quote!(#param));
+            let partial_check = all_set_check(&partial_states);
+            let before_states =
+                state_params
+                    .iter()
+                    .enumerate()
+                    .map(|(i, param)| if i == index { quote!(()) } else { param.to_token_stream() });
+            let partial_generics = state_generics(parse_quote!(#crate_module_path::Indexed<( #(#before_states,)* )>));
+            let partial_target = state_generics(parse_quote!(<#partial_check as #trait_name<#partial_states>>::Slot));
+            let mut partial_impl_generics = generics.clone();
+            for (i, param) in state_params.iter().enumerate() {
+                if i != index {
+                    partial_impl_generics
+                        .params
+                        .push(parse_quote!(#param: #crate_module_path::IndexSlot));
+                }
+            }
+            partial_impl_generics
+                .make_where_clause()
+                .predicates
+                .push(parse_quote!(#partial_check: #trait_name<#partial_states>));
+            let (impl_generics, _, where_clause) = partial_impl_generics.split_for_impl();
+            let states_pattern =
+                state_params
+                    .iter()
+                    .enumerate()
+                    .map(|(i, param)| if i == index { quote!(()) } else { param.to_token_stream() });
+            let partial_values = state_params.iter().enumerate().map(|(i, param)| {
+                if i == index {
+                    quote!((value,))
+                } else {
+                    param.to_token_stream()
+                }
+            });
+            let descructuring = self.included_fields().map(|f| f.name);
+            let partial_setter = quote! {
+                #[allow(dead_code, non_camel_case_types, missing_docs)]
+                #[automatically_derived]
+                impl #impl_generics #builder_name <#partial_generics> #where_clause {
+                    #deprecated
+                    #[doc = #doc]
+                    #[allow(clippy::used_underscore_binding, clippy::no_effect_underscore_binding, non_snake_case)]
+                    pub fn #setter_name (self, #token_param value: #item_type) -> #builder_name <#partial_target> {
+                        let ( #(#descructuring,)* ) = self.fields;
+                        let #crate_module_path::Indexed(( #(#states_pattern,)* )) = #field_name;
+                        let #field_name = <#partial_check as #trait_name<#partial_states>>::slot(( #(#partial_values,)* ));
+                        #builder_name {
+                            fields: ( #(#reconstructing,)* ),
+                            #context_passthrough
+                            phantom: self.phantom,
+                        }
+                    }
+                }
+            };
+
+            quote! {
+                #fresh_setter
+                #partial_setter
+            }
+        });
+
+        Ok(Some(quote! {
+            #trait_impl
+            #(#setters)*
+        }))
+    }
+
     fn required_field_impl(&self, field: &FieldInfo) -> TokenStream {
         let StructInfo { ref builder_name, .. } = self;
 
@@ -393,11 +1488,34 @@ impl<'a> StructInfo<'a> {
             ),
             proc_macro2::Span::call_site(),
         );
-        let early_build_error_message = format!("Missing required field {}", field_name);
-
         let build_method_name = self.build_method_name();
         let build_method_visibility = self.build_method_visibility();
 
+        if let Some(missing_message) = &field.builder_attr.missing_message {
+            // The bound is higher-ranked so that it is only checked when `build` is called, even if
+            // the builder type has no generic parameters.
+            let label = format!("`{}` was not set", field_name);
+            return quote! {
+                #[doc(hidden)]
+                #[allow(dead_code, non_camel_case_types)]
+                #[diagnostic::on_unimplemented(message = #missing_message, label = #label)]
+                pub trait #early_build_error_type_name {}
+                #[doc(hidden)]
+                #[allow(dead_code, non_camel_case_types, missing_docs, clippy::panic)]
+                #[automatically_derived]
+                impl #impl_generics #builder_name < #( #builder_generics ),* > #where_clause {
+                    #build_method_visibility fn #build_method_name(self) -> !
+                    where
+                        for<'__typed_builder_missing> &'__typed_builder_missing (): #early_build_error_type_name,
+                    {
+                        panic!()
+                    }
+                }
+            };
+        }
+
+        let early_build_error_message = format!("Missing required field {}", field_name);
+
         quote! {
             #[doc(hidden)]
             #[allow(dead_code, non_camel_case_types, non_snake_case)]
@@ -417,28 +1535,65 @@ impl<'a> StructInfo<'a> {
         }
     }
 
+    /// Resolves the names in a mutator's `requires` - which may be either field names or setter
+    /// names - to field names.
+    fn resolve_mutator_requirements(&self, required_fields: &HashSet<Ident>) -> syn::Result<HashSet<Ident>> {
+        required_fields
+            .iter()
+            .map(|required| {
+                let by_field_name = self.included_fields().find(|f| f.name == required);
+                let by_setter_name = self
+                    .setter_fields()
+                    .find(|f| f.setter_method_name() == *required && f.name != required);
+                match (by_field_name, by_setter_name) {
+                    (Some(by_field_name), Some(by_setter_name)) => Err(syn::Error::new_spanned(
+                        required,
+                        format!(
+                            "`{}` is ambiguous - it is the name of the field `{}` and of the setter of the field `{}`",
+                            required, by_field_name.name, by_setter_name.name
+                        ),
+                    )),
+                    (Some(field), None) | (None, Some(field)) => Ok(field.name.clone()),
+                    (None, None) => Err(syn::Error::new_spanned(
+                        required,
+                        format!("`{}` is neither a field nor a setter name", required),
+                    )),
+                }
+            })
+            .collect()
+    }
+
     fn mutator_impl(
         &self,
         mutator @ Mutator {
             fun: mutator_fn,
             required_fields,
+            ..
         }: &Mutator,
     ) -> syn::Result<TokenStream> {
         let StructInfo { ref builder_name, .. } = *self;
 
-        let mut required_fields = required_fields.clone();
+        let mut required_fields = self.resolve_mutator_requirements(required_fields)?;
 
         let mut ty_generics = self.generic_arguments();
         let mut destructuring = TokenStream::new();
         let mut ty_generics_tuple = empty_type_tuple();
         let mut generics = self.generics.clone();
         let mut mutator_ty_fields = Punctuated::<_, Token![,]>::new();
-        let mut mutator_destructure_fields = Punctuated::<_, Token![,]>::new();
+        let mut mutator_destructure_fields = Vec::new();
         for f @ FieldInfo { name, ty, .. } in self.included_fields() {
             if f.builder_attr.via_mutators.is_some() || required_fields.remove(f.name) {
+                if let Some(deferred) = f.builder_attr.setter.try_into_deferred {
+                    let mut error = syn::Error::new_spanned(
+                        &mutator_fn.sig.ident,
+                        "mutators cannot require fields with `try_into(deferred)` setters",
+                    );
+                    error.combine(syn::Error::new(deferred, "`try_into(deferred)` set here"));
+                    return Err(error);
+                }
                 ty_generics_tuple.elems.push(f.tuplized_type_ty_param());
                 mutator_ty_fields.push(quote!(#name: #ty));
-                mutator_destructure_fields.push(name);
+                mutator_destructure_fields.push((*name).clone());
                 quote!((#name,),).to_tokens(&mut destructuring);
             } else {
                 generics.params.push(f.generic_ty_param());
@@ -451,6 +1606,30 @@ impl<'a> StructInfo<'a> {
         let (impl_generics, _, where_clause) = generics.split_for_impl();
 
         let mutator_struct_name = format_ident!("TypedBuilderFieldMutator");
+        // Items nested in the setter cannot use its generic parameters, so the mutator struct
+        // redeclares the generic parameters of the struct and is marked with them.
+        let (struct_impl_generics, struct_ty_generics, struct_where_clause) = self.generics.split_for_impl();
+        let mutator_turbofish = struct_ty_generics.as_turbofish();
+        let mutator_phantom = self.phantom_type();
+        mutator_ty_fields.push(quote!(__typed_builder_phantom: #mutator_phantom));
+
+        // The context is moved into the mutator struct, so that the mutator can access it through `self.context()`.
+        let (context_take, context_accessor, context_passthrough) = if let Some(context) = &self.builder_attr.context {
+            mutator_ty_fields.push(quote!(context: #context));
+            mutator_destructure_fields.push(format_ident!("context"));
+            (
+                quote!(let context = self.context;),
+                quote! {
+                    #[allow(dead_code)]
+                    fn context(&self) -> &#context {
+                        &self.context
+                    }
+                },
+                quote!(context,),
+            )
+        } else {
+            (quote!(), quote!(), quote!())
+        };
 
         let ItemFn { attrs, vis, .. } = mutator_fn;
         let sig = mutator.outer_sig(parse_quote!(#builder_name <#ty_generics>));
@@ -464,17 +1643,22 @@ impl<'a> StructInfo<'a> {
                 #(#attrs)*
                 #[allow(clippy::used_underscore_binding, clippy::no_effect_underscore_binding)]
                 #vis #sig {
-                    struct #mutator_struct_name {
+                    struct #mutator_struct_name #struct_impl_generics #struct_where_clause {
                         #mutator_ty_fields
                     }
-                    impl #mutator_struct_name {
+                    impl #struct_impl_generics #mutator_struct_name #struct_ty_generics #struct_where_clause {
+                        #context_accessor
                         #mutator_fn
                     }
 
                     let __args = (#mutator_args);
 
                     let ( #destructuring ) = self.fields;
-                    let mut __mutator = #mutator_struct_name{ #mutator_destructure_fields };
+                    #context_take
+                    let mut __mutator = #mutator_struct_name #mutator_turbofish {
+                        #( #mutator_destructure_fields, )*
+                        __typed_builder_phantom: ::core::marker::PhantomData,
+                    };
 
                     // This dance is required to keep mutator args and destrucutre fields from interfering.
                     {
@@ -483,11 +1667,13 @@ impl<'a> StructInfo<'a> {
                     }
 
                     let #mutator_struct_name {
-                        #mutator_destructure_fields
+                        #( #mutator_destructure_fields, )*
+                        ..
                     } = __mutator;
 
                     #builder_name {
                         fields: ( #destructuring ),
+                        #context_passthrough
                         phantom: self.phantom,
                     }
                 }
@@ -495,21 +1681,328 @@ impl<'a> StructInfo<'a> {
         })
     }
 
-    fn build_method_name(&self) -> TokenStream {
-        self.builder_attr.build_method.common.get_name().unwrap_or(quote!(build))
+    fn mutators(&self) -> impl Iterator<Item = &Mutator> {
+        self.fields
+            .iter()
+            .flat_map(|f| &f.builder_attr.mutators)
+            .chain(&self.builder_attr.mutators)
     }
 
-    fn build_method_visibility(&self) -> TokenStream {
-        first_visibility(&[self.builder_attr.build_method.common.vis.as_ref(), Some(&public_visibility())])
+    /// Resolves the fields inside the build methods, from the field assignments (one for each field),
+    /// and runs the `#[mutator(auto)]` mutators as `mutators_run` asks - after all the fields are
+    /// resolved, or before the fields with a default are.
+    fn field_resolution(&self, assignments: Vec<TokenStream>) -> syn::Result<TokenStream> {
+        let auto_mutators = self.auto_mutators_code()?;
+        let each_max_checks = self.each_max_checks();
+        Ok(if self.builder_attr.mutators_run_before_defaults.is_some() {
+            let (defaulted, required): (Vec<_>, Vec<_>) =
+                (self.fields.iter().zip(assignments)).partition(|(field, _)| field.builder_attr.default.is_some());
+            let required = required.into_iter().map(|(_, assignment)| assignment);
+            let defaulted = defaulted.into_iter().map(|(_, assignment)| assignment);
+            quote! {
+                #( #required )*
+                #auto_mutators
+                #( #defaulted )*
+                #each_max_checks
+            }
+        } else {
+            quote! {
+                #( #assignments )*
+                #each_max_checks
+                #auto_mutators
+            }
+        })
+    }
+
+    /// Runs the `#[mutator(auto)]` mutators, in declaration order, on the resolved field values
+    /// inside the build methods. With `mutators_run = before_defaults`, only the fields without a
+    /// default are resolved at that point, so the mutators only get these.
+    fn auto_mutators_code(&self) -> syn::Result<TokenStream> {
+        let before_defaults = self.builder_attr.mutators_run_before_defaults.is_some();
+        let fields = (self.fields.iter())
+            .filter(|f| !before_defaults || f.builder_attr.default.is_none())
+            .collect::<Vec<_>>();
+        let field_names = fields.iter().map(|f| f.name).collect::<Vec<_>>();
+        let field_types = fields.iter().map(|f| f.ty);
+        let mutator_struct_name = format_ident!("TypedBuilderFieldMutator");
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let turbofish = ty_generics.as_turbofish();
+        let phantom = self.phantom_type();
+        let mutator_struct = quote! {
+            struct #mutator_struct_name #impl_generics #where_clause {
+                #( #field_names: #field_types, )*
+                __typed_builder_phantom: #phantom,
+            }
+        };
+        self.mutators()
+            .filter(|m| m.auto.is_some())
+            .map(|Mutator { fun, required_fields, .. }| {
+                let fn_name = &fun.sig.ident;
+                if before_defaults {
+                    let required_fields = self.resolve_mutator_requirements(required_fields)?;
+                    if let Some(field) = (self.fields.iter())
+                        .find(|f| f.builder_attr.default.is_some() && required_fields.contains(f.name))
+                    {
+                        return Err(syn::Error::new_spanned(
+                            fn_name,
+                            format!(
+                                "`{}` has a default, so it is not resolved yet when the mutators run - use `mutators_run = after_defaults`",
+                                field.name
+                            ),
+                        ));
+                    }
+                }
+                Ok(quote! {
+                    #[allow(unused_mut)]
+                    let ( #( mut #field_names, )* ) = {
+                        #mutator_struct
+                        impl #impl_generics #mutator_struct_name #ty_generics #where_clause {
+                            #fun
+                        }
+                        let mut __mutator = #mutator_struct_name #turbofish {
+                            #( #field_names, )*
+                            __typed_builder_phantom: ::core::marker::PhantomData,
+                        };
+                        __mutator.#fn_name();
+                        let #mutator_struct_name { #( #field_names, )* .. } = __mutator;
+                        ( #( #field_names, )* )
+                    };
+                })
+            })
+            .collect()
     }
 
-    fn build_method_impl(&self) -> TokenStream {
+    fn constructors_impl(&self) -> TokenStream {
         let StructInfo {
+            vis,
             ref name,
             ref builder_name,
             ..
         } = *self;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let visibility = first_visibility(&[
+            self.builder_attr.builder_method.common.vis.as_ref(),
+            self.builder_attr.builder_type.common.vis.as_ref(),
+            Some(vis),
+        ]);
+        let build_method_name = self.build_method_name();
+        let BuildMethodOutput {
+            generic,
+            output_type,
+            where_clause: build_method_where_clause,
+            ..
+        } = self.build_method_output();
+        let (context_param, context_init) = if let Some(context) = &self.builder_attr.context {
+            (quote!(context: #context,), quote!(context,))
+        } else {
+            (quote!(), quote!())
+        };
+
+        let constructors = self.builder_attr.constructors.iter().map(|(constructor_name, constructor_fields)| {
+            // The listed fields are passed in already set, and the builder's build method resolves the
+            // defaults of all the others.
+            let params = constructor_fields.iter().map(|field_name| {
+                let field = self.fields.iter().find(|f| f.name == field_name).expect("checked in StructInfo::new");
+                let ty = field.ty;
+                quote!(#field_name: #ty)
+            });
+            let init_fields_expr = self.included_fields().map(|f| {
+                if constructor_fields.contains(f.name) {
+                    let name = f.name;
+                    quote!((#name,))
+                } else if let Some(via_mutators) = &f.builder_attr.via_mutators {
+                    let init = &via_mutators.init;
+                    quote!((#init,))
+                } else {
+                    quote!(())
+                }
+            });
+            let doc = format!(
+                "Create a [`{}`] from the given fields, using the defaults for all the other fields.",
+                name
+            );
+            quote! {
+                #[doc = #doc]
+                #[allow(dead_code, clippy::default_trait_access, clippy::too_many_arguments)]
+                #visibility fn #constructor_name #generic (#context_param #(#params),*) -> #output_type #build_method_where_clause {
+                    #builder_name {
+                        fields: (#(#init_fields_expr,)*),
+                        #context_init
+                        phantom: ::core::default::Default::default(),
+                    }
+                    .#build_method_name()
+                }
+            }
+        });
+
+        if self.builder_attr.constructors.is_empty() {
+            return quote!();
+        }
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics #name #ty_generics #where_clause {
+                #(#constructors)*
+            }
+        }
+    }
+
+    /// The invariants are checked in a method of the built type, so that they can refer to the built
+    /// value as `self`.
+    fn invariants_impl(&self) -> TokenStream {
+        if self.builder_attr.invariants.is_empty() {
+            return quote!();
+        }
+        let name = self.name;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let checks = self.builder_attr.invariants.iter().map(|invariant| {
+            let message = format!("invariant `{}` does not hold", invariant.to_token_stream());
+            quote_spanned!(invariant.span() => ::core::debug_assert!(#invariant, "{}", #message);)
+        });
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics #name #ty_generics #where_clause {
+                #[doc(hidden)]
+                #[allow(dead_code, deprecated, clippy::used_underscore_binding)]
+                fn __typed_builder_check_invariants(&self) {
+                    #( #checks )*
+                }
+            }
+        }
+    }
+
+    /// Returns early from the build methods with the `short_circuit` expression when its field is set
+    /// to `true`.
+    fn short_circuit_code(&self) -> TokenStream {
+        let Some(short_circuit) = &self.builder_attr.short_circuit else {
+            return quote!();
+        };
+        let field = short_circuit.field.as_ref().expect("checked when parsing");
+        let returns = short_circuit.returns.as_ref().expect("checked when parsing");
+        let conversion = self.build_method_output().conversion;
+        quote! {
+            if #field {
+                let value = #returns;
+                return #conversion;
+            }
+        }
+    }
+
+    /// The `each(max = ...)` caps also apply to the values given to the regular setters.
+    fn each_max_checks(&self) -> TokenStream {
+        self.included_fields()
+            .filter_map(|field| {
+                let max = field.builder_attr.setter.each.as_ref()?.max.as_ref()?;
+                let name = field.name;
+                let message = format!("`{}` can have at most {} items", name, max);
+                Some(quote!(::core::assert!(#name.len() <= #max, #message);))
+            })
+            .collect()
+    }
+
+    fn invariants_check(&self) -> TokenStream {
+        if self.builder_attr.invariants.is_empty() {
+            quote!()
+        } else {
+            quote!(value.__typed_builder_check_invariants();)
+        }
+    }
+
+    /// Creates the built value from the resolved fields - with a struct literal, or by passing them in
+    /// declaration order to the `build_via` function.
+    fn value_construction(&self) -> TokenStream {
+        let field_names = self.fields.iter().map(|field| field.name);
+        if let (Some(function), Some(self_type)) = (self.function, self.self_type) {
+            let function_name = &function.sig.ident;
+            quote!(<#self_type>::#function_name( #( #field_names ),* ))
+        } else if let Some(function) = self.function {
+            // The function is moved into the build method, where it can only be reached through the builder.
+            let mut function = function.clone();
+            function.vis = syn::Visibility::Inherited;
+            function.attrs.retain(|attr| !attr.path().is_ident("doc"));
+            let function_name = &function.sig.ident;
+            // Lifetimes the builder type needs named may be elidable in the function itself.
+            quote!({
+                #[allow(clippy::needless_lifetimes)]
+                #function
+                #function_name( #( #field_names ),* )
+            })
+        } else {
+            let name = self.name;
+            let (_, ty_generics, _) = self.generics.split_for_impl();
+            let ty_generics = ty_generics.as_turbofish();
+            let field_inits = self
+                .fields
+                .iter()
+                .map(|field| {
+                    let name = field.name;
+                    if self.tuple_struct {
+                        let member = self.member(field);
+                        quote!(#member: #name)
+                    } else {
+                        quote!(#name)
+                    }
+                })
+                .collect::<Vec<_>>();
+            let construction = if let Some(build_via) = &self.builder_attr.build_via {
+                quote!(#build_via( #( #field_names ),* ))
+            } else if let Some(remote) = &self.builder_attr.remote {
+                let mut remote = remote.clone();
+                for segment in remote.segments.iter_mut() {
+                    if let syn::PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                        args.colon2_token = Some(Default::default());
+                    }
+                }
+                quote!(#remote { #( #field_inits ),* })
+            } else {
+                let variant = self.variant.map(|variant| quote!(::#variant));
+                quote!(#name #ty_generics #variant { #( #field_inits ),* })
+            };
+            if self.builder_attr.remote.is_some() {
+                // Going through the local struct keeps it and its fields from being reported as unused.
+                quote!({
+                    let #name #ty_generics { #( #field_inits ),* } = #name #ty_generics { #( #field_inits ),* };
+                    #construction
+                })
+            } else {
+                construction
+            }
+        }
+    }
+
+    /// `builder`, `<variant in snake_case>_builder` for enum variants, the name of the function, or
+    /// `<constructor>_builder` for constructors other than `new`.
+    fn default_builder_method_name(&self) -> TokenStream {
+        if let (Some(function), Some(_)) = (self.function, self.self_type) {
+            if function.sig.ident == "new" {
+                quote!(builder)
+            } else {
+                format_ident!("{}_builder", strip_raw_ident_prefix(function.sig.ident.to_string())).to_token_stream()
+            }
+        } else if let Some(function) = self.function {
+            function.sig.ident.to_token_stream()
+        } else if let Some(variant) = self.variant {
+            format_ident!("{}_builder", to_snake_case(&strip_raw_ident_prefix(variant.to_string()))).to_token_stream()
+        } else {
+            quote!(builder)
+        }
+    }
+
+    fn build_method_name(&self) -> TokenStream {
+        let default = if self.function.is_some() && self.self_type.is_none() {
+            quote!(call)
+        } else {
+            quote!(build)
+        };
+        self.builder_attr.build_method.common.get_name().unwrap_or(default)
+    }
+
+    fn build_method_visibility(&self) -> TokenStream {
+        first_visibility(&[self.builder_attr.build_method.common.vis.as_ref(), Some(&public_visibility())])
+    }
 
+    /// The generics of the `impl` block of the build method, and the builder's generic arguments in
+    /// it - the state in which all the required fields are set.
+    fn build_method_generics(&self) -> (syn::Generics, syn::AngleBracketedGenericArguments) {
         let generics = {
             let mut generics = self.generics.clone();
             for field in self.included_fields() {
@@ -525,7 +2018,7 @@ impl<'a> StructInfo<'a> {
                                 arguments: syn::PathArguments::AngleBracketed(syn::AngleBracketedGenericArguments {
                                     colon2_token: None,
                                     lt_token: Default::default(),
-                                    args: [syn::GenericArgument::Type(field.ty.clone())].into_iter().collect(),
+                                    args: [syn::GenericArgument::Type(field.stored_type())].into_iter().collect(),
                                     gt_token: Default::default(),
                                 }),
                             });
@@ -539,9 +2032,7 @@ impl<'a> StructInfo<'a> {
             }
             generics
         };
-        let (impl_generics, _, _) = generics.split_for_impl();
-
-        let (_, ty_generics, where_clause) = self.generics.split_for_impl();
+        let (_, ty_generics, _) = self.generics.split_for_impl();
 
         let modified_ty_generics = modify_types_generics_hack(&ty_generics, |args| {
             args.push(syn::GenericArgument::Type(
@@ -556,7 +2047,11 @@ impl<'a> StructInfo<'a> {
             ));
         });
 
-        let descructuring = self.included_fields().map(|f| f.name);
+        (generics, modified_ty_generics)
+    }
+
+    fn build_method_impl(&self) -> syn::Result<TokenStream> {
+        let (generics, modified_ty_generics) = self.build_method_generics();
 
         // The default of a field can refer to earlier-defined fields, which we handle by
         // writing out a bunch of `let` statements first, which can each refer to earlier ones.
@@ -573,62 +2068,873 @@ impl<'a> StructInfo<'a> {
             };
 
             if let Some(ref default) = field.builder_attr.default {
-                if field.builder_attr.setter.skip.is_some() {
+                let crate_module_path = &self.builder_attr.crate_module_path;
+                if field.builder_attr.default_try.is_some() {
+                    if field.builder_attr.setter.skip.is_some() {
+                        quote!(let #maybe_mut #name = (#default)?;)
+                    } else {
+                        quote! {
+                            let #maybe_mut #name = match #crate_module_path::Optional::into_option(#name) {
+                                ::core::option::Option::Some(#name) => #name,
+                                ::core::option::Option::None => (#default)?,
+                            };
+                        }
+                    }
+                } else if field.builder_attr.setter.skip.is_some() {
                     quote!(let #maybe_mut #name = #default;)
+                } else if field.deferred_error.is_some() {
+                    quote!(let #maybe_mut #name = #crate_module_path::Optional::into_value(#name, || ::core::result::Result::Ok(#default))?;)
                 } else {
-                    let crate_module_path = &self.builder_attr.crate_module_path;
-
                     quote!(let #maybe_mut #name = #crate_module_path::Optional::into_value(#name, || #default);)
                 }
+            } else if field.deferred_error.is_some() {
+                quote!(let #maybe_mut #name = #name.0?;)
             } else {
                 quote!(let #maybe_mut #name = #name.0;)
             }
         });
-        let field_names = self.fields.iter().map(|field| field.name);
 
         let build_method_name = self.build_method_name();
-        let build_method_visibility = self.build_method_visibility();
         let build_method_doc = if self.builder_attr.doc {
             self.builder_attr
                 .build_method
                 .common
-                .get_doc_or(|| format!("Finalise the builder and create its [`{}`] instance", name))
+                .get_doc_or(|| format!("Finalise the builder and create its [`{}`] instance", self.display_name()))
         } else {
             quote!()
         };
 
-        let type_constructor = {
-            let ty_generics = ty_generics.as_turbofish();
-            quote!(#name #ty_generics)
-        };
+        self.build_method_impl_for(
+            &generics,
+            &modified_ty_generics,
+            build_method_doc,
+            build_method_name,
+            assignments.collect(),
+        )
+    }
 
-        let (build_method_generic, output_type, build_method_where_clause) = match &self.builder_attr.build_method.into {
-            IntoSetting::NoConversion => (None, quote!(#name #ty_generics), None),
-            IntoSetting::GenericConversion => (
-                Some(quote!(<__R>)),
-                quote!(__R),
-                Some(quote!(where #name #ty_generics: Into<__R>)),
-            ),
-            IntoSetting::TypeConversionToSpecificType(into) => (None, into.to_token_stream(), None),
-        };
+    /// The `impl` block of a build method, shared by the build method and by `build_or_default`.
+    /// The `assignments` resolve each field from the builder's state, and are ordered around the
+    /// mutators by `field_resolution`.
+    fn build_method_impl_for(
+        &self,
+        generics: &syn::Generics,
+        builder_ty_generics: &syn::AngleBracketedGenericArguments,
+        method_doc: TokenStream,
+        method_name: impl ToTokens,
+        assignments: Vec<TokenStream>,
+    ) -> syn::Result<TokenStream> {
+        let StructInfo { ref builder_name, .. } = *self;
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+        let descructuring = self.included_fields().map(|f| f.name);
+        let context_binding = self.context_binding();
+        let value_construction = self.value_construction();
+        let field_resolution = self.field_resolution(assignments)?;
+        let invariants_check = self.invariants_check();
+        let short_circuit = self.short_circuit_code();
+        let method_visibility = self.build_method_visibility();
+
+        let BuildMethodOutput {
+            generic,
+            output_type,
+            where_clause: method_where_clause,
+            conversion,
+        } = self.build_method_output();
 
-        quote!(
+        Ok(quote!(
             #[allow(dead_code, non_camel_case_types, missing_docs)]
             #[automatically_derived]
-            impl #impl_generics #builder_name #modified_ty_generics #where_clause {
-                #build_method_doc
-                #[allow(clippy::default_trait_access, clippy::used_underscore_binding, clippy::no_effect_underscore_binding)]
-                #build_method_visibility fn #build_method_name #build_method_generic (self) -> #output_type #build_method_where_clause {
+            impl #impl_generics #builder_name #builder_ty_generics #where_clause {
+                #method_doc
+                #[allow(
+                    clippy::default_trait_access,
+                    clippy::used_underscore_binding,
+                    clippy::no_effect_underscore_binding,
+                    clippy::redundant_closure,
+                )]
+                #method_visibility fn #method_name #generic (self) -> #output_type #method_where_clause {
+                    #context_binding
                     let ( #(#descructuring,)* ) = self.fields;
-                    #( #assignments )*
+                    #field_resolution
+                    #short_circuit
 
                     #[allow(deprecated)]
-                    #type_constructor {
-                        #( #field_names ),*
-                    }.into()
+                    let value = #value_construction;
+                    #invariants_check
+                    #conversion
                 }
             }
-        )
+        ))
+    }
+
+    fn build_method_output(&self) -> BuildMethodOutput {
+        let name = self.name;
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        let (generic, output_type, where_clause) = match &self.builder_attr.build_method.into {
+            IntoSetting::NoConversion if self.builder_attr.build_method.returns_impl.is_some() => {
+                let returns_impl = &self.builder_attr.build_method.returns_impl;
+                (None, quote!(#returns_impl), None)
+            }
+            IntoSetting::NoConversion => match self.function {
+                Some(syn::ItemFn {
+                    sig:
+                        syn::Signature {
+                            output: syn::ReturnType::Type(_, output),
+                            ..
+                        },
+                    ..
+                }) => (None, output.to_token_stream(), None),
+                Some(_) => (None, quote!(()), None),
+                None => match &self.builder_attr.remote {
+                    Some(remote) => (None, remote.to_token_stream(), None),
+                    None => (None, quote!(#name #ty_generics), None),
+                },
+            },
+            IntoSetting::GenericConversion => (
+                Some(quote!(<__R>)),
+                quote!(__R),
+                Some(quote!(where #name #ty_generics: Into<__R>)),
+            ),
+            IntoSetting::TypeConversionToSpecificType(into) => (None, into.to_token_stream(), None),
+        };
+
+        // An opaque return type does not give `.into()` anything to infer the target type from.
+        let conversion = if self.builder_attr.build_method.returns_impl.is_some() {
+            quote!(value)
+        } else {
+            quote!(value.into())
+        };
+
+        let (output_type, conversion) = if self.builder_attr.build_method.pinned.is_some() {
+            (
+                quote!(::core::pin::Pin<::std::boxed::Box<#output_type>>),
+                quote!(::std::boxed::Box::pin(#conversion)),
+            )
+        } else {
+            (output_type, conversion)
+        };
+
+        // The `post` closure gets the value last - after the auto mutators and the invariant checks -
+        // and the conversion is only done if it succeeds.
+        let (output_type, conversion) = if let Some(post) = &self.builder_attr.build_method.post {
+            let closure = &post.closure;
+            let error_type = &post.error_type;
+            (
+                quote!(::core::result::Result<#output_type, #error_type>),
+                quote! {
+                    match (#closure)(value) {
+                        ::core::result::Result::Ok(value) => ::core::result::Result::Ok(#conversion),
+                        ::core::result::Result::Err(error) => ::core::result::Result::Err(error),
+                    }
+                },
+            )
+        } else if let Some(error_type) = self.deferred_error_type() {
+            (
+                quote!(::core::result::Result<#output_type, #error_type>),
+                quote!(::core::result::Result::Ok(#conversion)),
+            )
+        } else {
+            (output_type, conversion)
+        };
+
+        BuildMethodOutput {
+            generic,
+            output_type,
+            where_clause,
+            conversion,
+        }
+    }
+
+    fn partial_type_impl(&self) -> Option<TokenStream> {
+        let partial_type = self.builder_attr.partial_type.as_ref()?;
+        let StructInfo { vis, ref name, .. } = *self;
+
+        let partial_type_name = partial_type
+            .common
+            .get_name()
+            .unwrap_or_else(|| format_ident!("{}Partial", name).to_token_stream());
+        let partial_type_visibility = first_visibility(&[partial_type.common.vis.as_ref(), Some(vis)]);
+        let partial_type_doc = partial_type.common.get_doc_or(|| {
+            format!(
+                "All the fields of [`{}`] as optional overrides. Apply them with [`{}::with_overrides`].",
+                name, name
+            )
+        });
+        let derives = &partial_type.derives;
+        let derives = (!derives.is_empty()).then(|| quote!(#[derive(#(#derives),*)]));
+
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let generics = self.generics;
+
+        let partial_fields = self.fields.iter().map(|field| {
+            let name = field.name;
+            let ty = field.ty;
+            quote!(#partial_type_visibility #name: ::core::option::Option<#ty>)
+        });
+        let overrides = self.fields.iter().map(|field| {
+            let name = field.name;
+            let member = self.member(field);
+            quote! {
+                if let ::core::option::Option::Some(#name) = overrides.#name {
+                    self.#member = #name;
+                }
+            }
+        });
+        let with_overrides_doc = format!(
+            "Replace the fields that are set in the [`{}`] overrides, and keep the others.",
+            partial_type_name
+        );
+
+        Some(quote! {
+            #partial_type_doc
+            #derives
+            #partial_type_visibility struct #partial_type_name #generics #where_clause {
+                #( #partial_fields, )*
+            }
+
+            #[automatically_derived]
+            impl #impl_generics #name #ty_generics #where_clause {
+                #[doc = #with_overrides_doc]
+                #[allow(dead_code, deprecated)]
+                #partial_type_visibility fn with_overrides(mut self, overrides: #partial_type_name #ty_generics) -> Self {
+                    #( #overrides )*
+                    self
+                }
+            }
+        })
+    }
+
+    fn recipe_impl(&self) -> Option<TokenStream> {
+        let recipe = self.builder_attr.recipe.as_ref()?;
+        let StructInfo {
+            vis, ref builder_name, ..
+        } = *self;
+
+        let recipe_name = recipe
+            .common
+            .get_name()
+            .unwrap_or_else(|| format_ident!("{}", Self::default_type_name(self.name, self.variant, "Recipe")).to_token_stream());
+        let recipe_visibility = first_visibility(&[recipe.common.vis.as_ref(), Some(vis)]);
+        let recipe_doc = recipe.common.get_doc_or(|| {
+            format!(
+                "A finished [`{}`] builder, that can build any number of [`{}`] instances.",
+                builder_name,
+                self.display_name()
+            )
+        });
+
+        let (_, ty_generics, where_clause) = self.generics.split_for_impl();
+        let recipe_generics = {
+            // Like the builder type, the recipe type defaults to the fields of a fresh builder, so
+            // that it can follow defaulted generic parameters of the struct.
+            let mut all_fields_param: syn::TypeParam = parse_quote!(TypedBuilderFields);
+            all_fields_param.default = self.init_fields_type_default().map(syn::Type::Tuple);
+            let mut generics = self.generics.clone();
+            generics.params.push(all_fields_param.into());
+            generics
+        };
+        let (recipe_impl_generics, recipe_ty_generics, _) = recipe_generics.split_for_impl();
+        let mut clone_where_clause: syn::WhereClause = parse_quote!(where TypedBuilderFields: ::core::clone::Clone);
+        if let Some(predicates) = where_clause {
+            clone_where_clause.predicates.extend(predicates.predicates.clone());
+        }
+
+        let (build_generics, builder_ty_generics) = self.build_method_generics();
+        let (build_impl_generics, _, _) = build_generics.split_for_impl();
+        let mut build_where_clause: syn::WhereClause =
+            parse_quote!(where #builder_name #builder_ty_generics: ::core::clone::Clone);
+        if let Some(predicates) = where_clause {
+            build_where_clause.predicates.extend(predicates.predicates.clone());
+        }
+        let recipe_ty_generics_in_build = modify_types_generics_hack(&ty_generics, |args| {
+            args.extend(builder_ty_generics.args.iter().last().cloned());
+        });
+        let into_recipe_body = if recipe.capture_defaults.is_some() {
+            // Resolve the defaults like the build method does - in order, so they can refer to
+            // earlier fields - and store them as set fields. The skipped fields are not stored in
+            // the builder, so they are only resolved for the defaults that refer to them.
+            let crate_module_path = &self.builder_attr.crate_module_path;
+            let context_binding = self.context_binding();
+            let context_passthrough = self.context_passthrough();
+            let descructuring = self.included_fields().map(|field| field.name);
+            let resolution = self.fields.iter().map(|field| {
+                let name = field.name;
+                let maybe_mut = if let Some(span) = field.builder_attr.mutable_during_default_resolution {
+                    quote_spanned!(span => mut)
+                } else {
+                    quote!()
+                };
+                match &field.builder_attr.default {
+                    Some(default) if field.builder_attr.setter.skip.is_some() => quote!(let #maybe_mut #name = #default;),
+                    Some(default) => {
+                        quote!(let #maybe_mut #name = #crate_module_path::Optional::into_value(#name, || #default);)
+                    }
+                    None => quote!(let #maybe_mut #name = #name.0;),
+                }
+            });
+            let reconstructing = self.included_fields().map(|field| field.name);
+            quote! {
+                #context_binding
+                let ( #(#descructuring,)* ) = self.fields;
+                #( #resolution )*
+                #recipe_name {
+                    builder: #builder_name {
+                        fields: ( #((#reconstructing,),)* ),
+                        #context_passthrough
+                        phantom: self.phantom,
+                    },
+                }
+            }
+        } else {
+            quote!(#recipe_name { builder: self })
+        };
+        let into_recipe_ty_generics = if recipe.capture_defaults.is_some() {
+            modify_types_generics_hack(&ty_generics, |args| {
+                args.push(syn::GenericArgument::Type(
+                    type_tuple(self.included_fields().map(|field| field.tuplized_type_ty_param())).into(),
+                ));
+            })
+        } else {
+            recipe_ty_generics_in_build.clone()
+        };
+
+        let build_method_name = self.build_method_name();
+        let build_method_visibility = self.build_method_visibility();
+        let BuildMethodOutput {
+            generic,
+            output_type,
+            where_clause: build_method_where_clause,
+            ..
+        } = self.build_method_output();
+        let build_method_turbofish = generic.as_ref().map(|_| quote!(::<__R>));
+        let build_doc = format!("Create a new [`{}`] instance from the recipe.", self.display_name());
+        let build_iter_doc = format!(
+            "Consume the recipe into an iterator that lazily builds `count` new [`{}`] instances from it.",
+            self.display_name()
+        );
+        let into_recipe_doc = format!(
+            "Finalise the builder into a [`{}`], that can build any number of [`{}`] instances.",
+            recipe_name,
+            self.display_name()
+        );
+
+        Some(quote! {
+            #recipe_doc
+            #[allow(dead_code, non_camel_case_types, non_snake_case)]
+            #recipe_visibility struct #recipe_name #recipe_generics #where_clause {
+                builder: #builder_name #recipe_ty_generics,
+            }
+
+            #[automatically_derived]
+            impl #recipe_impl_generics ::core::clone::Clone for #recipe_name #recipe_ty_generics #clone_where_clause {
+                fn clone(&self) -> Self {
+                    Self {
+                        builder: self.builder.clone(),
+                    }
+                }
+            }
+
+            #[allow(dead_code, non_camel_case_types, missing_docs)]
+            #[automatically_derived]
+            impl #build_impl_generics #builder_name #builder_ty_generics #where_clause {
+                #[doc = #into_recipe_doc]
+                #[allow(unused_variables, clippy::used_underscore_binding, clippy::no_effect_underscore_binding)]
+                #build_method_visibility fn into_recipe(self) -> #recipe_name #into_recipe_ty_generics {
+                    #into_recipe_body
+                }
+            }
+
+            #[allow(dead_code, non_camel_case_types, missing_docs)]
+            #[automatically_derived]
+            impl #build_impl_generics #recipe_name #recipe_ty_generics_in_build #build_where_clause {
+                #[doc = #build_doc]
+                #build_method_visibility fn #build_method_name #generic (&self) -> #output_type #build_method_where_clause {
+                    self.builder.clone().#build_method_name #build_method_turbofish()
+                }
+
+                #[doc = #build_iter_doc]
+                #build_method_visibility fn build_iter #generic (self, count: usize) -> impl ::core::iter::Iterator<Item = #output_type> #build_method_where_clause {
+                    (0..count).map(move |_| self.#build_method_name #build_method_turbofish())
+                }
+            }
+        })
+    }
+
+    fn build_or_default_method_impl(&self) -> syn::Result<Option<TokenStream>> {
+        if self.builder_attr.build_method.or_default.is_none() {
+            return Ok(None);
+        }
+        let crate_module_path = &self.builder_attr.crate_module_path;
+
+        let mut generics = self.generics.clone();
+        for field in self.included_fields() {
+            let ty = field.ty;
+            let generic_ident = &field.generic_ident;
+            generics
+                .params
+                .push(parse_quote!(#generic_ident: #crate_module_path::Optional<#ty>));
+            if field.builder_attr.default.is_none() {
+                generics
+                    .make_where_clause()
+                    .predicates
+                    .push(parse_quote!(#ty: ::core::default::Default));
+            }
+        }
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        let modified_ty_generics = modify_types_generics_hack(&ty_generics, |args| {
+            args.push(syn::GenericArgument::Type(
+                type_tuple(self.included_fields().map(|field| field.type_ident())).into(),
+            ));
+        });
+
+        let assignments = self.fields.iter().map(|field| {
+            let name = &field.name;
+            let maybe_mut = if let Some(span) = field.builder_attr.mutable_during_default_resolution {
+                quote_spanned!(span => mut)
+            } else {
+                quote!()
+            };
+            let default = field
+                .builder_attr
+                .default
+                .as_ref()
+                .map_or_else(|| quote!(::core::default::Default::default()), |default| quote!(#default));
+            if field.builder_attr.setter.skip.is_some() {
+                quote!(let #maybe_mut #name = #default;)
+            } else {
+                quote!(let #maybe_mut #name = #crate_module_path::Optional::into_value(#name, || #default);)
+            }
+        });
+
+        let build_method_name = self.build_method_name();
+        let method_name = format_ident!("{}_or_default", build_method_name.to_string());
+        let method_doc = format!(
+            "Finalise the builder and create its [`{}`] instance, using `Default::default()` for the required fields that were not set.",
+            self.display_name()
+        );
+
+        self.build_method_impl_for(
+            &generics,
+            &modified_ty_generics,
+            quote!(#[doc = #method_doc]),
+            method_name,
+            assignments.collect(),
+        )
+        .map(Some)
+    }
+
+    fn merge_from_method_impl(&self) -> Option<TokenStream> {
+        let merge_from_method = self.builder_attr.merge_from_method.as_ref()?;
+        let name = self.name;
+
+        let mut generics = self.generics.clone();
+        for field in self.fields.iter() {
+            let ty = field.ty;
+            let predicate: syn::WherePredicate = if field.is_std_collection() {
+                parse_quote!(#ty: ::core::clone::Clone + ::core::iter::IntoIterator + ::core::iter::Extend<<#ty as ::core::iter::IntoIterator>::Item>)
+            } else {
+                parse_quote!(#ty: ::core::cmp::PartialEq + ::core::default::Default + ::core::clone::Clone)
+            };
+            generics.make_where_clause().predicates.push(predicate);
+        }
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        let merges = self.fields.iter().map(|field| {
+            let member = self.member(field);
+            if field.is_std_collection() {
+                quote!(::core::iter::Extend::extend(&mut self.#member, ::core::clone::Clone::clone(&other.#member));)
+            } else {
+                quote! {
+                    if other.#member != ::core::default::Default::default() {
+                        self.#member = ::core::clone::Clone::clone(&other.#member);
+                    }
+                }
+            }
+        });
+
+        let method_name = merge_from_method.get_name().unwrap_or_else(|| quote!(merge_from));
+        let method_visibility = first_visibility(&[merge_from_method.vis.as_ref(), Some(&public_visibility())]);
+        let method_doc = merge_from_method.get_doc_or(|| {
+            format!(
+                "Merge `other` into this [`{}`] with protobuf semantics: fields of `other` that are not equal to their `Default` overwrite the fields of `self`, and collection fields are extended with the items of `other`.",
+                name
+            )
+        });
+
+        Some(quote! {
+            #[automatically_derived]
+            impl #impl_generics #name #ty_generics #where_clause {
+                #method_doc
+                #[allow(dead_code, deprecated, clippy::default_trait_access)]
+                #method_visibility fn #method_name(&mut self, other: &Self) {
+                    #( #merges )*
+                }
+            }
+        })
+    }
+
+    fn derives_impl(&self) -> TokenStream {
+        let StructInfo { ref builder_name, .. } = *self;
+        let crate_module_path = &self.builder_attr.crate_module_path;
+
+        let debug_impl = self.builder_attr.builder_type.derive_debug.map(|_| {
+            let is_shown = |field: &FieldInfo| !self.builder_attr.builder_type.debug_skip.contains(field.name);
+            let mut generics = self.generics.clone();
+            for field in self.included_fields() {
+                let ty = field.stored_type();
+                let generic_ident = &field.generic_ident;
+                generics
+                    .params
+                    .push(parse_quote!(#generic_ident: #crate_module_path::Optional<#ty>));
+                if is_shown(field) && field.builder_attr.secret.is_none() {
+                    generics
+                        .make_where_clause()
+                        .predicates
+                        .push(parse_quote!(#ty: ::core::fmt::Debug));
+                }
+            }
+            let (impl_generics, _, where_clause) = generics.split_for_impl();
+            let (_, ty_generics, _) = self.generics.split_for_impl();
+            let modified_ty_generics = modify_types_generics_hack(&ty_generics, |args| {
+                args.push(syn::GenericArgument::Type(
+                    type_tuple(self.included_fields().map(|field| field.type_ident())).into(),
+                ));
+            });
+            let descructuring = self.included_fields().map(|field| {
+                if is_shown(field) {
+                    let name = field.name;
+                    quote!(ref #name)
+                } else {
+                    quote!(_)
+                }
+            });
+            let debug_fields = self.included_fields().filter(|field| is_shown(field)).map(|field| {
+                let name = field.name;
+                let name_str = strip_raw_ident_prefix(name.to_string());
+                if field.builder_attr.secret.is_some() {
+                    quote! {
+                        if #crate_module_path::Optional::as_option(#name).is_some() {
+                            debug_struct.field(#name_str, &::core::format_args!("***"));
+                        }
+                    }
+                } else {
+                    quote! {
+                        if let ::core::option::Option::Some(#name) = #crate_module_path::Optional::as_option(#name) {
+                            debug_struct.field(#name_str, #name);
+                        }
+                    }
+                }
+            });
+            let builder_name_str = builder_name.to_string();
+            let cfg = self
+                .builder_attr
+                .builder_type
+                .debug_cfg
+                .as_ref()
+                .map(|cfg| quote!(#[cfg(#cfg)]));
+            quote! {
+                #cfg
+                #[automatically_derived]
+                impl #impl_generics ::core::fmt::Debug for #builder_name #modified_ty_generics #where_clause {
+                    #[allow(deprecated, clippy::used_underscore_binding, clippy::no_effect_underscore_binding)]
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        let ( #(#descructuring,)* ) = self.fields;
+                        let mut debug_struct = f.debug_struct(#builder_name_str);
+                        #( #debug_fields )*
+                        debug_struct.finish()
+                    }
+                }
+            }
+        });
+
+        // These impls only look at the fields - they are bound on the type-state, which is a tuple of
+        // `()` for the unset fields and `(T,)` for the set ones.
+        let fields_trait_impl = |derive: Option<Span>, cfg: &Option<syn::Meta>, trait_path: TokenStream, body: TokenStream| {
+            derive.map(|_| {
+                let mut generics = self.generics.clone();
+                generics.params.push(parse_quote!(TypedBuilderFields));
+                generics
+                    .make_where_clause()
+                    .predicates
+                    .push(parse_quote!(TypedBuilderFields: #trait_path));
+                let (impl_generics, _, where_clause) = generics.split_for_impl();
+                let (_, ty_generics, _) = self.generics.split_for_impl();
+                let modified_ty_generics = modify_types_generics_hack(&ty_generics, |args| {
+                    args.push(parse_quote!(TypedBuilderFields));
+                });
+                let cfg = cfg.as_ref().map(|cfg| quote!(#[cfg(#cfg)]));
+                quote! {
+                    #cfg
+                    #[automatically_derived]
+                    impl #impl_generics #trait_path for #builder_name #modified_ty_generics #where_clause {
+                        #body
+                    }
+                }
+            })
+        };
+        let builder_type = &self.builder_attr.builder_type;
+        let partial_eq_impl = fields_trait_impl(
+            builder_type.derive_partial_eq,
+            &builder_type.partial_eq_cfg,
+            quote!(::core::cmp::PartialEq),
+            quote! {
+                fn eq(&self, other: &Self) -> bool {
+                    self.fields == other.fields
+                }
+            },
+        );
+        let eq_impl = fields_trait_impl(
+            builder_type.derive_eq,
+            &builder_type.eq_cfg,
+            quote!(::core::cmp::Eq),
+            quote!(),
+        );
+        let hash_impl = fields_trait_impl(
+            builder_type.derive_hash,
+            &builder_type.hash_cfg,
+            quote!(::core::hash::Hash),
+            quote! {
+                fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+                    ::core::hash::Hash::hash(&self.fields, state);
+                }
+            },
+        );
+
+        quote! {
+            #debug_impl
+            #partial_eq_impl
+            #eq_impl
+            #hash_impl
+        }
+    }
+
+    fn apply_method_impl(&self) -> syn::Result<Option<TokenStream>> {
+        let Some(apply_method) = self.builder_attr.apply_method.as_ref() else {
+            return Ok(None);
+        };
+        let StructInfo {
+            ref name,
+            ref builder_name,
+            ..
+        } = *self;
+        let crate_module_path = &self.builder_attr.crate_module_path;
+
+        let mut generics = self.generics.clone();
+        for field in self.included_fields() {
+            let ty = field.ty;
+            let generic_ident = &field.generic_ident;
+            generics
+                .params
+                .push(parse_quote!(#generic_ident: #crate_module_path::Optional<#ty>));
+        }
+        let (impl_generics, _, _) = generics.split_for_impl();
+        let (_, ty_generics, where_clause) = self.generics.split_for_impl();
+        let modified_ty_generics = modify_types_generics_hack(&ty_generics, |args| {
+            args.push(syn::GenericArgument::Type(
+                type_tuple(self.included_fields().map(|field| field.type_ident())).into(),
+            ));
+        });
+
+        let descructuring = self.included_fields().map(|f| f.name);
+        let (context_binding, resolution, assignments) = if apply_method.defaults.is_some() {
+            // Like in the build method, the defaults can refer to earlier fields - but only to the
+            // ones that are always set, since the required ones may not be.
+            let required_fields = self
+                .fields
+                .iter()
+                .filter(|field| field.builder_attr.default.is_none() && field.builder_attr.via_mutators.is_none())
+                .map(|field| field.name)
+                .collect::<Vec<_>>();
+            let mut resolution = Vec::new();
+            let mut assignments = Vec::new();
+            for field in self.fields.iter() {
+                let name = field.name;
+                let member = self.member(field);
+                if field.builder_attr.via_mutators.is_some() {
+                    resolution.push(quote!(let #name = #name.0;));
+                    assignments.push(quote!(target.#member = #name;));
+                    continue;
+                }
+                let Some(default) = &field.builder_attr.default else {
+                    resolution.push(quote!(let #name = #crate_module_path::Optional::into_option(#name);));
+                    assignments.push(quote! {
+                        if let ::core::option::Option::Some(#name) = #name {
+                            target.#member = #name;
+                        }
+                    });
+                    continue;
+                };
+                let default_tokens = field.builder_attr.default_once_expr.as_ref().unwrap_or(default);
+                if let Some(required_field) = find_variable_use(default_tokens.to_token_stream(), &required_fields) {
+                    return Err(syn::Error::new_spanned(
+                        default_tokens,
+                        format!(
+                            "the default of a field cannot refer to fields without a default (found `{}`) with `apply_method(defaults)` - they may not be set",
+                            required_field
+                        ),
+                    ));
+                }
+                let maybe_mut = if let Some(span) = field.builder_attr.mutable_during_default_resolution {
+                    quote_spanned!(span => mut)
+                } else {
+                    quote!()
+                };
+                if field.builder_attr.setter.skip.is_some() {
+                    resolution.push(quote!(let #maybe_mut #name = #default;));
+                } else {
+                    resolution.push(quote!(let #maybe_mut #name = #crate_module_path::Optional::into_value(#name, || #default);));
+                }
+                assignments.push(quote!(target.#member = #name;));
+            }
+            (self.context_binding(), resolution, assignments)
+        } else {
+            let assignments = self
+                .included_fields()
+                .map(|field| {
+                    let name = field.name;
+                    let member = self.member(field);
+                    quote! {
+                        if let ::core::option::Option::Some(#name) = #crate_module_path::Optional::into_option(#name) {
+                            target.#member = #name;
+                        }
+                    }
+                })
+                .collect();
+            (quote!(), Vec::new(), assignments)
+        };
+
+        let method_name = apply_method.common.get_name().unwrap_or_else(|| quote!(apply_to));
+        let method_visibility = first_visibility(&[apply_method.common.vis.as_ref(), Some(&public_visibility())]);
+        let method_doc = apply_method.common.get_doc_or(|| {
+            if apply_method.defaults.is_some() {
+                format!(
+                    "Assign the fields that were set on the builder to an existing [`{}`] instance, and the defaults to the unset fields that have one, leaving the other fields untouched.",
+                    name
+                )
+            } else {
+                format!(
+                    "Assign the fields that were set on the builder to an existing [`{}`] instance, leaving the other fields untouched.",
+                    name
+                )
+            }
+        });
+
+        Ok(Some(quote! {
+            #[allow(dead_code, non_camel_case_types, missing_docs)]
+            #[automatically_derived]
+            impl #impl_generics #builder_name #modified_ty_generics #where_clause {
+                #method_doc
+                #[allow(deprecated, clippy::used_underscore_binding, clippy::no_effect_underscore_binding)]
+                #method_visibility fn #method_name(self, target: &mut #name #ty_generics) {
+                    #context_binding
+                    let ( #(#descructuring,)* ) = self.fields;
+                    #( #resolution )*
+                    #( #assignments )*
+                }
+            }
+        }))
+    }
+
+    /// Declares the type alias of an instance of the struct, and the matching alias of the builder
+    /// type - whose fields parameter defaults to those of a fresh builder, like the builder type's.
+    pub fn builder_alias_impl(&self, alias: &BuilderAlias) -> syn::Result<TokenStream> {
+        let BuilderAlias { ident, generics, ty } = alias;
+        let segment = ty.path.segments.last().expect("paths are not empty");
+        if ty.qself.is_some() || segment.ident != *self.name {
+            return Err(syn::Error::new_spanned(
+                ty,
+                format!("expected an instance of `{}`, the struct this attribute is on", self.name),
+            ));
+        }
+        if self.generics.params.is_empty() {
+            return Err(syn::Error::new_spanned(
+                self.name,
+                format!("`{}` is not generic - use `{}::builder()` directly", self.name, self.name),
+            ));
+        }
+        let args = match &segment.arguments {
+            syn::PathArguments::None => Vec::new(),
+            syn::PathArguments::AngleBracketed(args) => args.args.iter().collect(),
+            syn::PathArguments::Parenthesized(args) => {
+                return Err(syn::Error::new_spanned(args, "expected generic arguments in angle brackets"));
+            }
+        };
+        if let Some(extra) = args.get(self.generics.params.len()) {
+            return Err(syn::Error::new_spanned(
+                extra,
+                format!(
+                    "expected at most {} generic arguments for `{}`",
+                    self.generics.params.len(),
+                    self.name
+                ),
+            ));
+        }
+
+        // The builder's generic arguments are those of the struct, with the defaults filled in - which
+        // can refer to the parameters before them.
+        let mut replacements = Vec::<(String, TokenStream)>::new();
+        for (i, param) in self.generics.params.iter().enumerate() {
+            let (name, default) = match param {
+                syn::GenericParam::Lifetime(param) => (param.lifetime.to_string(), None),
+                syn::GenericParam::Type(param) => {
+                    (param.ident.to_string(), param.default.as_ref().map(ToTokens::to_token_stream))
+                }
+                syn::GenericParam::Const(param) => {
+                    (param.ident.to_string(), param.default.as_ref().map(ToTokens::to_token_stream))
+                }
+            };
+            let arg = match (args.get(i), default) {
+                (
+                    Some(
+                        syn::GenericArgument::AssocType(_)
+                        | syn::GenericArgument::AssocConst(_)
+                        | syn::GenericArgument::Constraint(_),
+                    ),
+                    _,
+                ) => {
+                    return Err(syn::Error::new_spanned(args[i], "expected a generic argument of the struct"));
+                }
+                (Some(arg), _) => arg.to_token_stream(),
+                (None, Some(default)) => replace_generic_params(default, &replacements),
+                (None, None) => {
+                    return Err(syn::Error::new_spanned(
+                        ty,
+                        format!("missing generic argument for `{}`", name),
+                    ));
+                }
+            };
+            replacements.push((name, arg));
+        }
+        let builder_args = replacements.iter().map(|(_, arg)| arg);
+
+        let mut builder_alias_generics = generics.clone();
+        let mut fields_param: syn::TypeParam = parse_quote!(TypedBuilderFields);
+        if let Some(init_fields_type) = self.init_fields_type_default() {
+            fields_param.default = Some(syn::parse2(replace_generic_params(
+                init_fields_type.to_token_stream(),
+                &replacements,
+            ))?);
+        }
+        builder_alias_generics.params.push(fields_param.into());
+
+        let builder_name = &self.builder_name;
+        let builder_alias_name = format_ident!("{}Builder", ident);
+        let alias_visibility = self.vis;
+        let builder_alias_visibility = first_visibility(&[self.builder_attr.builder_type.common.vis.as_ref(), Some(self.vis)]);
+        let builder_alias_doc = format!("Builder for [`{}`] instances.", ident);
+        let mut builder_alias = quote! {
+            #[doc = #builder_alias_doc]
+            #[allow(dead_code)]
+            #builder_alias_visibility type #builder_alias_name #builder_alias_generics = #builder_name<#( #builder_args, )* TypedBuilderFields>;
+        };
+        if let Some(cfg) = &self.builder_attr.cfg {
+            builder_alias = gate_items_behind_cfg(builder_alias, cfg)?;
+        }
+        Ok(quote! {
+            #alias_visibility type #ident #generics = #ty;
+            #builder_alias
+        })
     }
 
     pub fn derive(&self) -> syn::Result<TokenStream> {
@@ -642,20 +2948,47 @@ impl<'a> StructInfo<'a> {
             .filter(|f| f.builder_attr.default.is_none())
             .map(|f| self.required_field_impl(f));
         let mutators = self
-            .fields
-            .iter()
-            .flat_map(|f| &f.builder_attr.mutators)
-            .chain(&self.builder_attr.mutators)
+            .mutators()
+            .filter(|m| m.auto.is_none())
             .map(|m| self.mutator_impl(m))
             .collect::<Result<TokenStream, _>>()?;
-        let build_method = self.build_method_impl();
+        let build_method = self.build_method_impl()?;
+        let constructors = self.constructors_impl();
+        let invariants = self.invariants_impl();
+        let build_or_default_method = self.build_or_default_method_impl()?;
+        let recipe = self.recipe_impl();
+        let partial_type = self.partial_type_impl();
+        let apply_method = self.apply_method_impl()?;
+        let merge_from_method = self.merge_from_method_impl();
+        let derives = self.derives_impl();
 
-        Ok(quote! {
+        let output = quote! {
             #builder_creation
             #fields
             #(#required_fields)*
             #mutators
             #build_method
-        })
+            #constructors
+            #invariants
+            #build_or_default_method
+            #recipe
+            #partial_type
+            #apply_method
+            #merge_from_method
+            #derives
+        };
+
+        if let Some(cfg) = &self.builder_attr.cfg {
+            gate_items_behind_cfg(output, cfg)
+        } else {
+            Ok(output)
+        }
     }
 }
+
+struct BuildMethodOutput {
+    generic: Option<TokenStream>,
+    output_type: TokenStream,
+    where_clause: Option<TokenStream>,
+    conversion: TokenStream,
+}
@@ -0,0 +1,405 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::parse::Error;
+
+use crate::builder_attr::{IntoSetting, TypeBuilderAttr};
+use crate::field_info::FieldInfo;
+use crate::util::{
+    first_visibility, gate_items_behind_cfg, modify_types_generics_hack, public_visibility, strip_raw_ident_prefix,
+};
+
+/// Builder for a union. Unlike struct builders, the type-state of a union builder only tracks whether
+/// the active member was already chosen - `()` before any setter is called and `(Union,)` after.
+#[derive(Debug)]
+pub struct UnionInfo<'a> {
+    vis: &'a syn::Visibility,
+    name: &'a syn::Ident,
+    generics: &'a syn::Generics,
+    fields: Vec<FieldInfo<'a>>,
+
+    builder_attr: TypeBuilderAttr<'a>,
+    builder_name: syn::Ident,
+}
+
+impl<'a> UnionInfo<'a> {
+    pub fn new(ast: &'a syn::DeriveInput, fields: impl Iterator<Item = &'a syn::Field>) -> syn::Result<UnionInfo<'a>> {
+        let builder_attr = TypeBuilderAttr::new(&ast.attrs)?;
+        let builder_name = builder_attr
+            .builder_type
+            .common
+            .get_name()
+            .map(|name| strip_raw_ident_prefix(name.to_string()))
+            .unwrap_or_else(|| strip_raw_ident_prefix(format!("{}Builder", ast.ident)));
+        let fields = fields
+            .enumerate()
+            .map(|(i, f)| FieldInfo::new(i, f, builder_attr.field_defaults.clone()))
+            .collect::<Result<Vec<_>, _>>()?;
+        for field in fields.iter() {
+            // Everything the setters of union members do not implement. `computed` and `skip` are
+            // checked before `default`, which they come with.
+            let FieldInfo { builder_attr, .. } = field;
+            let setter = &builder_attr.setter;
+            let unsupported = [
+                ("computed", builder_attr.computed),
+                ("setter(lazy)", setter.lazy),
+                ("setter(skip)", setter.skip),
+                ("default_once", builder_attr.default_once),
+                ("default_try", builder_attr.default_try),
+                ("default", builder_attr.default.as_ref().map(|_| field.name.span())),
+                ("via_mutators", builder_attr.via_mutators.as_ref().map(|v| v.span)),
+                ("mutators", builder_attr.mutators.first().map(|_| field.name.span())),
+                (
+                    "mutable_during_default_resolution",
+                    builder_attr.mutable_during_default_resolution,
+                ),
+                (
+                    "missing_message",
+                    builder_attr.missing_message.as_ref().map(|message| message.span()),
+                ),
+                ("secret", builder_attr.secret),
+                ("setter(each)", setter.each.as_ref().map(|each| each.span)),
+                ("setter(entry)", setter.entry.as_ref().map(|entry| entry.span)),
+                ("setter(indexed)", setter.indexed),
+                ("setter(unit)", setter.units.first().map(|unit| unit.name.span())),
+                ("setter(setter_fn)", setter.setter_fn),
+                ("setter(field_mut)", setter.field_mut),
+                ("setter(maybe_into)", setter.maybe_into),
+                ("setter(merge)", setter.merge),
+                ("setter(try_into(deferred))", setter.try_into_deferred),
+            ];
+            if let Some((caption, span)) = unsupported.iter().find_map(|(caption, span)| Some((caption, (*span)?))) {
+                return Err(Error::new(span, format!("`{}` is not supported for union members", caption)));
+            }
+        }
+        if let Some(span) = builder_attr
+            .builder_type
+            .derive_debug
+            .or(builder_attr.builder_type.derive_partial_eq)
+            .or(builder_attr.builder_type.derive_eq)
+            .or(builder_attr.builder_type.derive_hash)
+        {
+            return Err(Error::new(span, "Only `Clone` can be derived for union builders"));
+        }
+        if builder_attr.apply_method.is_some()
+            || builder_attr.merge_from_method.is_some()
+            || builder_attr.recipe.is_some()
+            || builder_attr.partial_type.is_some()
+        {
+            return Err(Error::new_spanned(
+                &ast.ident,
+                "`apply_method`, `merge_from_method`, `recipe` and `partial_type` are not supported for unions",
+            ));
+        }
+        if let Some(span) = builder_attr.build_method.or_default {
+            return Err(Error::new(span, "`or_default` is not supported for unions"));
+        }
+        if let Some(param) = builder_attr.builder_method.params.first() {
+            return Err(Error::new_spanned(
+                param,
+                "`builder_method(params(...))` is not supported for unions",
+            ));
+        }
+        if let Some(context) = &builder_attr.context {
+            return Err(Error::new_spanned(context, "`context` is not supported for unions"));
+        }
+        if let Some(max_fields) = &builder_attr.max_fields {
+            return Err(Error::new_spanned(max_fields, "`max_fields` is not supported for unions"));
+        }
+        if let Some(name) = builder_attr.drop_order.first() {
+            return Err(Error::new_spanned(name, "`drop_order` is not supported for unions"));
+        }
+        if let Some((name, _)) = builder_attr.constructors.first() {
+            return Err(Error::new_spanned(name, "`constructor` is not supported for unions"));
+        }
+        if let Some(invariant) = builder_attr.invariants.first() {
+            return Err(Error::new_spanned(invariant, "`invariant` is not supported for unions"));
+        }
+        if let Some(build_via) = &builder_attr.build_via {
+            return Err(Error::new_spanned(build_via, "`build_via` is not supported for unions"));
+        }
+        if let Some(short_circuit) = &builder_attr.short_circuit {
+            return Err(Error::new(short_circuit.span, "`short_circuit` is not supported for unions"));
+        }
+        if let Some(reset) = builder_attr.reset {
+            return Err(Error::new(reset, "`reset` is not supported for unions"));
+        }
+        if let Some(emit_schema) = builder_attr.emit_schema {
+            return Err(Error::new(emit_schema, "`emit_schema` is not supported for unions"));
+        }
+        if let Some(remote) = &builder_attr.remote {
+            return Err(Error::new_spanned(remote, "`remote` is not supported for unions"));
+        }
+        if let Some(post) = &builder_attr.build_method.post {
+            return Err(Error::new_spanned(&post.closure, "`post` is not supported for unions"));
+        }
+        if !builder_attr.mutators.is_empty() {
+            return Err(Error::new_spanned(&ast.ident, "`mutators` are not supported for unions"));
+        }
+        Ok(UnionInfo {
+            vis: &ast.vis,
+            name: &ast.ident,
+            generics: &ast.generics,
+            fields,
+            builder_attr,
+            builder_name: syn::Ident::new(&builder_name, proc_macro2::Span::call_site()),
+        })
+    }
+
+    pub fn derive(&self) -> syn::Result<TokenStream> {
+        let UnionInfo {
+            vis,
+            name,
+            ref builder_name,
+            ..
+        } = *self;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let unset_generics = modify_types_generics_hack(&ty_generics, |args| {
+            args.push(syn::parse_quote!(()));
+        });
+        let set_generics = modify_types_generics_hack(&ty_generics, |args| {
+            args.push(syn::parse_quote!((#name #ty_generics,)));
+        });
+
+        let b_generics = {
+            let mut generics = self.generics.clone();
+            generics.params.push(syn::parse_quote!(TypedBuilderFields = ()));
+            generics
+        };
+        let (b_generics_impl, b_generics_ty, b_generics_where_extras_predicates) = b_generics.split_for_impl();
+        let mut b_generics_where: syn::WhereClause = syn::parse_quote!(where TypedBuilderFields: Clone);
+        if let Some(predicates) = b_generics_where_extras_predicates {
+            b_generics_where.predicates.extend(predicates.predicates.clone());
+        }
+        let impl_traits = self.builder_attr.builder_type.impl_traits.iter().map(|trait_path| {
+            quote! {
+                #[automatically_derived]
+                impl #b_generics_impl #trait_path for #builder_name #b_generics_ty #b_generics_where_extras_predicates {}
+            }
+        });
+        let phantom_generics = self.generics.params.iter().filter_map(|param| match param {
+            syn::GenericParam::Lifetime(lifetime) => {
+                let lifetime = &lifetime.lifetime;
+                Some(quote!(&#lifetime ()))
+            }
+            syn::GenericParam::Type(ty) => {
+                let ty = &ty.ident;
+                Some(ty.to_token_stream())
+            }
+            syn::GenericParam::Const(_cnst) => None,
+        });
+
+        let builder_method_name = self
+            .builder_attr
+            .builder_method
+            .common
+            .get_name()
+            .unwrap_or_else(|| quote!(builder));
+        let builder_method_visibility = first_visibility(&[
+            self.builder_attr.builder_method.common.vis.as_ref(),
+            self.builder_attr.builder_type.common.vis.as_ref(),
+            Some(vis),
+        ]);
+        let build_method_name = self.builder_attr.build_method.common.get_name().unwrap_or(quote!(build));
+        let build_method_visibility =
+            first_visibility(&[self.builder_attr.build_method.common.vis.as_ref(), Some(&public_visibility())]);
+        let builder_method_doc = self.builder_attr.builder_method.common.get_doc_or(|| {
+            format!(
+                "
+                Create a builder for building `{name}`.
+                On the builder, call exactly one of {setters} to set the active member.
+                Finally, call `.{build_method_name}()` to create the instance of `{name}`.
+                ",
+                setters = self
+                    .fields
+                    .iter()
+                    .map(|field| format!("`.{}(...)`", field.setter_method_name()))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )
+        });
+        let builder_type_visibility = first_visibility(&[self.builder_attr.builder_type.common.vis.as_ref(), Some(vis)]);
+        let builder_type_doc = if self.builder_attr.doc {
+            self.builder_attr.builder_type.common.get_doc_or(|| {
+                format!(
+                    "
+                    Builder for [`{name}`] instances.
+
+                    See [`{name}::{builder_method_name}()`] for more info.
+                    "
+                )
+            })
+        } else {
+            quote!(#[doc(hidden)])
+        };
+        let build_method_doc = if self.builder_attr.doc {
+            self.builder_attr
+                .build_method
+                .common
+                .get_doc_or(|| format!("Finalise the builder and create its [`{}`] instance", name))
+        } else {
+            quote!()
+        };
+
+        let setters = self
+            .fields
+            .iter()
+            .map(|field| {
+                let field_name = field.name;
+                let method_name = field.setter_method_name();
+                let token_param = field.token_param();
+                let asyncness = field.setter_asyncness();
+                let doc = field.builder_attr.setter.doc.as_ref().map(|doc| quote!(#[doc = #doc]));
+                let deprecated = &field.builder_attr.deprecated;
+                let (param_list, arg_expr) = field.setter_param_list_and_value(&self.builder_attr.crate_module_path)?;
+                let (setter_generic, setter_return_type, setter_return_wrapper) =
+                    match field.fallible_setter_generic_and_error()? {
+                        Some((generic, error_type)) => (
+                            generic,
+                            quote!(::core::result::Result<#builder_name #set_generics, #error_type>),
+                            quote!(::core::result::Result::Ok),
+                        ),
+                        None => (field.named_into_generic()?, quote!(#builder_name #set_generics), quote!()),
+                    };
+                Ok(quote! {
+                    #deprecated
+                    #doc
+                    pub #asyncness fn #method_name #setter_generic (self, #token_param #param_list) -> #setter_return_type {
+                        #setter_return_wrapper(#builder_name {
+                            fields: (#name { #field_name: #arg_expr },),
+                            phantom: self.phantom,
+                        })
+                    }
+                })
+            })
+            .collect::<syn::Result<TokenStream>>()?;
+
+        let repeated_member_error_type_name = syn::Ident::new(
+            &format!("{}_Error_Union_member_already_set", builder_name),
+            proc_macro2::Span::call_site(),
+        );
+        let repeated_member_setters = self.fields.iter().map(|field| {
+            let method_name = field.setter_method_name();
+            quote! {
+                #[deprecated(note = "Union member already set")]
+                pub fn #method_name (self, _: #repeated_member_error_type_name) -> Self {
+                    self
+                }
+            }
+        });
+        let missing_member_error_type_name = syn::Ident::new(
+            &format!("{}_Error_Missing_union_member", builder_name),
+            proc_macro2::Span::call_site(),
+        );
+
+        let (build_method_generic, output_type, build_method_where_clause) = match &self.builder_attr.build_method.into {
+            IntoSetting::NoConversion if self.builder_attr.build_method.returns_impl.is_some() => {
+                let returns_impl = &self.builder_attr.build_method.returns_impl;
+                (None, quote!(#returns_impl), None)
+            }
+            IntoSetting::NoConversion => (None, quote!(#name #ty_generics), None),
+            IntoSetting::GenericConversion => (
+                Some(quote!(<__R>)),
+                quote!(__R),
+                Some(quote!(where #name #ty_generics: Into<__R>)),
+            ),
+            IntoSetting::TypeConversionToSpecificType(into) => (None, into.to_token_stream(), None),
+        };
+
+        let conversion = if self.builder_attr.build_method.returns_impl.is_some() {
+            quote!(value)
+        } else {
+            quote!(value.into())
+        };
+
+        let (output_type, conversion) = if self.builder_attr.build_method.pinned.is_some() {
+            (
+                quote!(::core::pin::Pin<::std::boxed::Box<#output_type>>),
+                quote!(::std::boxed::Box::pin(#conversion)),
+            )
+        } else {
+            (output_type, conversion)
+        };
+
+        let output = quote! {
+            #[automatically_derived]
+            impl #impl_generics #name #ty_generics #where_clause {
+                #builder_method_doc
+                #[allow(dead_code, clippy::default_trait_access)]
+                #builder_method_visibility fn #builder_method_name() -> #builder_name #unset_generics {
+                    #builder_name {
+                        fields: (),
+                        phantom: ::core::default::Default::default(),
+                    }
+                }
+            }
+
+            #[must_use]
+            #builder_type_doc
+            #[allow(dead_code, non_camel_case_types, non_snake_case)]
+            #builder_type_visibility struct #builder_name #b_generics #b_generics_where_extras_predicates {
+                fields: TypedBuilderFields,
+                phantom: ::core::marker::PhantomData<(#( ::core::marker::PhantomData<#phantom_generics> ),*)>,
+            }
+
+            #[automatically_derived]
+            impl #b_generics_impl Clone for #builder_name #b_generics_ty #b_generics_where {
+                #[allow(clippy::default_trait_access)]
+                fn clone(&self) -> Self {
+                    Self {
+                        fields: self.fields.clone(),
+                        phantom: ::core::default::Default::default(),
+                    }
+                }
+            }
+
+            #( #impl_traits )*
+
+            #[allow(dead_code, non_camel_case_types, missing_docs)]
+            #[automatically_derived]
+            impl #impl_generics #builder_name #unset_generics #where_clause {
+                #setters
+            }
+
+            #[doc(hidden)]
+            #[allow(dead_code, non_camel_case_types, non_snake_case)]
+            #[allow(clippy::exhaustive_enums)]
+            pub enum #repeated_member_error_type_name {}
+            #[doc(hidden)]
+            #[allow(dead_code, non_camel_case_types, missing_docs)]
+            #[automatically_derived]
+            impl #impl_generics #builder_name #set_generics #where_clause {
+                #( #repeated_member_setters )*
+            }
+
+            #[doc(hidden)]
+            #[allow(dead_code, non_camel_case_types, non_snake_case)]
+            #[allow(clippy::exhaustive_enums)]
+            pub enum #missing_member_error_type_name {}
+            #[doc(hidden)]
+            #[allow(dead_code, non_camel_case_types, missing_docs, clippy::panic)]
+            #[automatically_derived]
+            impl #impl_generics #builder_name #unset_generics #where_clause {
+                #[deprecated(note = "Missing union member")]
+                #build_method_visibility fn #build_method_name(self, _: #missing_member_error_type_name) -> ! {
+                    panic!()
+                }
+            }
+
+            #[allow(dead_code, non_camel_case_types, missing_docs)]
+            #[automatically_derived]
+            impl #impl_generics #builder_name #set_generics #where_clause {
+                #build_method_doc
+                #build_method_visibility fn #build_method_name #build_method_generic (self) -> #output_type #build_method_where_clause {
+                    let (value,) = self.fields;
+                    #conversion
+                }
+            }
+        };
+
+        if let Some(cfg) = &self.builder_attr.cfg {
+            gate_items_behind_cfg(output, cfg)
+        } else {
+            Ok(output)
+        }
+    }
+}
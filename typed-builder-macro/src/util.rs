@@ -1,6 +1,6 @@
 use std::iter;
 
-use proc_macro2::{Ident, Span, TokenStream, TokenTree};
+use proc_macro2::{Ident, Spacing, Span, TokenStream, TokenTree};
 use quote::{format_ident, ToTokens};
 use syn::{
     parenthesized,
@@ -86,6 +86,172 @@ pub fn strip_raw_ident_prefix(mut name: String) -> String {
     name
 }
 
+/// `VariantName` -> `variant_name`, for method names derived from enum variants.
+pub fn to_snake_case(name: &str) -> String {
+    let mut result = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 && !result.ends_with('_') {
+                result.push('_');
+            }
+            result.extend(c.to_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// `function_name` -> `FunctionName`, for type names derived from functions.
+pub fn to_pascal_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .flat_map(|part| {
+            let mut chars = part.chars();
+            chars.next().into_iter().flat_map(char::to_uppercase).chain(chars)
+        })
+        .collect()
+}
+
+/// Whether `tokens` use an associated type of one of the type parameters in `generics` by its
+/// shorthand - `T::Item` rather than `<T as Iterator>::Item`. The shorthand is resolved through
+/// the bounds of the parameter, which are not in scope in the defaults of generic parameters.
+pub fn uses_associated_type_shorthand(tokens: TokenStream, generics: &syn::Generics) -> bool {
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        let found = match token {
+            TokenTree::Ident(ident) => {
+                generics.type_params().any(|param| param.ident == ident)
+                    && matches!(tokens.peek(), Some(TokenTree::Punct(punct)) if punct.as_char() == ':' && punct.spacing() == Spacing::Joint)
+            }
+            TokenTree::Group(group) => uses_associated_type_shorthand(group.stream(), generics),
+            _ => false,
+        };
+        if found {
+            return true;
+        }
+    }
+    false
+}
+
+/// The first of `names` that `tokens` use as a variable - that is, not after a `.` like a field or a
+/// method of some other value.
+pub fn find_variable_use<'a>(tokens: TokenStream, names: &[&'a Ident]) -> Option<&'a Ident> {
+    let mut after_dot = false;
+    for token in tokens {
+        let found = match &token {
+            TokenTree::Ident(ident) if !after_dot => names.iter().find(|name| **name == ident).copied(),
+            TokenTree::Group(group) => find_variable_use(group.stream(), names),
+            _ => None,
+        };
+        if found.is_some() {
+            return found;
+        }
+        after_dot = matches!(&token, TokenTree::Punct(punct) if punct.as_char() == '.');
+    }
+    None
+}
+
+/// Replaces `Self` in `tokens` with `self_type`, for types moved out of an `impl` block.
+pub fn replace_self_type(tokens: TokenStream, self_type: &syn::Type) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Ident(ident) if ident == "Self" => self_type.to_token_stream(),
+            TokenTree::Group(group) => {
+                let mut replaced = proc_macro2::Group::new(group.delimiter(), replace_self_type(group.stream(), self_type));
+                replaced.set_span(group.span());
+                TokenTree::Group(replaced).into()
+            }
+            token => token.into(),
+        })
+        .collect()
+}
+
+/// Replaces the generic parameters of an item in `tokens` with the generic arguments of an instance
+/// of it. The parameters are named like they are written - `'a` for lifetimes, `T` for types and
+/// consts.
+pub fn replace_generic_params(tokens: TokenStream, replacements: &[(String, TokenStream)]) -> TokenStream {
+    let replacement_of = |name: String| replacements.iter().find(|(param, _)| *param == name).map(|(_, arg)| arg);
+    let mut result = TokenStream::new();
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == '\'' => {
+                let lifetime = match tokens.peek() {
+                    Some(TokenTree::Ident(ident)) => replacement_of(format!("'{}", ident)),
+                    _ => None,
+                };
+                if let Some(lifetime) = lifetime {
+                    tokens.next();
+                    result.extend(lifetime.clone());
+                } else {
+                    result.extend(iter::once(TokenTree::Punct(punct)));
+                }
+            }
+            TokenTree::Ident(ident) => match replacement_of(ident.to_string()) {
+                // A qualified path like `T::Item` needs the replacement in angle brackets.
+                Some(arg) if matches!(tokens.peek(), Some(TokenTree::Punct(punct)) if punct.as_char() == ':') => {
+                    result.extend(quote::quote!(<#arg>));
+                }
+                Some(arg) => result.extend(arg.clone()),
+                None => result.extend(iter::once(TokenTree::Ident(ident))),
+            },
+            TokenTree::Group(group) => {
+                let mut replaced =
+                    proc_macro2::Group::new(group.delimiter(), replace_generic_params(group.stream(), replacements));
+                replaced.set_span(group.span());
+                result.extend(iter::once(TokenTree::Group(replaced)));
+            }
+            token => result.extend(iter::once(token)),
+        }
+    }
+    result
+}
+
+/// Renders tokens the way they would be written by hand - `impl Into<String>` rather than
+/// `impl :: core :: convert :: Into < String >` - for human-readable descriptions.
+pub fn tokens_to_readable_string(tokens: &proc_macro2::TokenStream) -> String {
+    let mut result = tokens.to_string();
+    for prefix in [
+        ":: core :: convert :: ",
+        ":: core :: option :: ",
+        ":: core :: ops :: ",
+        ":: core :: iter :: ",
+        ":: core :: fmt :: ",
+        ":: core :: future :: ",
+        ":: core :: clone :: ",
+    ] {
+        result = result.replace(prefix, "");
+    }
+    for (from, to) in [
+        (" :: ", "::"),
+        (" : ", ": "),
+        (" < ", "<"),
+        ("< ", "<"),
+        (" >", ">"),
+        (" ,", ","),
+        ("& ", "&"),
+        ("( ", "("),
+        (" )", ")"),
+        ("[ ", "["),
+        (" ]", "]"),
+    ] {
+        result = result.replace(from, to);
+    }
+    result
+}
+
+/// Put every item in `tokens` behind `#[cfg(#predicate)]`.
+pub fn gate_items_behind_cfg(tokens: TokenStream, predicate: &syn::Meta) -> syn::Result<TokenStream> {
+    let file: syn::File = syn::parse2(tokens)?;
+    Ok(file
+        .items
+        .into_iter()
+        .flat_map(|item| quote::quote!(#[cfg(#predicate)] #item))
+        .collect())
+}
+
 pub fn first_visibility(visibilities: &[Option<&syn::Visibility>]) -> proc_macro2::TokenStream {
     let vis = visibilities
         .iter()
@@ -100,6 +266,24 @@ pub fn public_visibility() -> syn::Visibility {
     syn::Visibility::Public(syn::token::Pub::default())
 }
 
+pub fn expr_to_field_names(expr: syn::Expr) -> Result<Vec<Ident>, Error> {
+    match expr {
+        syn::Expr::Array(syn::ExprArray { elems, .. }) => elems
+            .into_iter()
+            .map(|expr| match expr {
+                syn::Expr::Path(path) if path.path.get_ident().is_some() => {
+                    Ok(path.path.get_ident().cloned().expect("should be ident"))
+                }
+                expr => Err(Error::new_spanned(expr, "Expected field name")),
+            })
+            .collect(),
+        expr => Err(Error::new_spanned(
+            expr,
+            "Only list of field names [field1, field2, …] supported",
+        )),
+    }
+}
+
 pub fn expr_to_lit_string(expr: &syn::Expr) -> Result<String, Error> {
     match expr {
         syn::Expr::Lit(lit) => match &lit.lit {
@@ -137,6 +321,22 @@ impl AttrArg {
         syn::Error::new_spanned(self, message)
     }
 
+    /// Error for a parameter that is not one of `known`, suggesting the closest one if it looks like a typo.
+    pub fn unknown_parameter(&self, known: &[&str]) -> syn::Error {
+        let name = self.name().to_string();
+        let suggestion = known
+            .iter()
+            .map(|candidate| (levenshtein_distance(&name, candidate), candidate))
+            .filter(|(distance, candidate)| *distance <= (candidate.len() / 3).max(1))
+            .min_by_key(|(distance, _)| *distance);
+        let message = if let Some((_, suggestion)) = suggestion {
+            format!("Unknown parameter {:?} - did you mean {:?}?", name, suggestion)
+        } else {
+            format!("Unknown parameter {:?}", name)
+        };
+        syn::Error::new_spanned(self.name(), message)
+    }
+
     pub fn flag(self) -> syn::Result<Ident> {
         if let Self::Flag(name) = self {
             Ok(name)
@@ -191,6 +391,24 @@ impl AttrArg {
     }
 }
 
+fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous_row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current_row = Vec::with_capacity(b.len() + 1);
+        current_row.push(i + 1);
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution_cost = if a_char == *b_char { 0 } else { 1 };
+            let distance = (previous_row[j] + substitution_cost)
+                .min(previous_row[j + 1] + 1)
+                .min(current_row[j] + 1);
+            current_row.push(distance);
+        }
+        previous_row = current_row;
+    }
+    previous_row[b.len()]
+}
+
 pub struct KeyValue {
     pub name: Ident,
     pub eq: Token![=],